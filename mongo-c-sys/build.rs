@@ -129,7 +129,7 @@ fn win(_mongoc_version: &str) {
 }
 
 fn main() {
-    let mongoc_version = "1.16.2";
+    let mongoc_version = "1.22.0";
 
     #[cfg(target_env = "msvc")]
     win(mongoc_version);
@@ -0,0 +1,581 @@
+//! Async facade running the blocking driver calls on the runtime's blocking pool.
+
+use crate::{
+    client::{Client, Clientc},
+    client_pool::{ClientPool, ClientPoolc},
+    collection::{Collection, Collectionc},
+    database::{Database, Databasec},
+    error::Result,
+    options::{
+        Aggregate, CountDocuments, FindOptions, Insert, Remove, RemoveOne, ReplaceOne, Update,
+        UpdateOne,
+    },
+    pipeline::Pipeline,
+    results::{DeleteResult, InsertManyResult, InsertOneResult, UpdateResult},
+    session::{Session, Sessionc},
+};
+use bson::Document;
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+#[cfg(feature = "tokio-runtime")]
+async fn spawn_blocking<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .expect("blocking driver call panicked")
+}
+
+#[cfg(all(feature = "async-std-runtime", not(feature = "tokio-runtime")))]
+async fn spawn_blocking<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    async_std::task::spawn_blocking(f).await
+}
+
+/// Limits the number of driver calls running at once.
+#[derive(Debug)]
+struct Semaphore {
+    state: Mutex<Waiters>,
+}
+
+#[derive(Debug)]
+struct Waiters {
+    permits: usize,
+    /// Waker of every pending `acquire` by arrival, removed once woken for a permit
+    queue: BTreeMap<u64, Waker>,
+    next_id: u64,
+}
+
+struct Permit(Arc<Semaphore>);
+
+/// Future of `Semaphore::acquire`, leaves the queue when dropped before getting a permit.
+struct Acquire<'a> {
+    semaphore: &'a Arc<Semaphore>,
+    /// Place in the queue, from the first pending poll until a permit is taken
+    id: Option<u64>,
+}
+
+impl Semaphore {
+    fn new(permits: usize) -> Self {
+        Semaphore {
+            state: Mutex::new(Waiters {
+                permits: permits.max(1),
+                queue: BTreeMap::new(),
+                next_id: 0,
+            }),
+        }
+    }
+
+    fn acquire(self: &Arc<Self>) -> Acquire<'_> {
+        Acquire {
+            semaphore: self,
+            id: None,
+        }
+    }
+}
+
+impl Waiters {
+    /// Wakes the first waiter when a permit is free.
+    fn wake_next(&mut self) {
+        if self.permits == 0 {
+            return;
+        }
+
+        let first = self.queue.keys().next().cloned();
+        if let Some(waker) = first.and_then(|id| self.queue.remove(&id)) {
+            waker.wake();
+        }
+    }
+}
+
+impl Future for Acquire<'_> {
+    type Output = Permit;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Permit> {
+        let this = self.get_mut();
+        let mut waiters = this.semaphore.state.lock().expect("semaphore lock");
+
+        if waiters.permits > 0 {
+            waiters.permits -= 1;
+            if let Some(id) = this.id.take() {
+                waiters.queue.remove(&id);
+            }
+            return Poll::Ready(Permit(this.semaphore.clone()));
+        }
+
+        let id = match this.id {
+            Some(id) => id,
+            None => {
+                waiters.next_id += 1;
+                waiters.next_id
+            }
+        };
+        this.id = Some(id);
+        // Replaces the waker of an earlier poll, or queues the waiter again after a
+        // permit it was woken for was taken by someone else.
+        waiters.queue.insert(id, cx.waker().clone());
+
+        Poll::Pending
+    }
+}
+
+impl Drop for Acquire<'_> {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            let mut waiters = self.semaphore.state.lock().expect("semaphore lock");
+
+            // Not queued anymore means it was woken, the permit goes to the next waiter.
+            if waiters.queue.remove(&id).is_none() {
+                waiters.wake_next();
+            }
+        }
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut waiters = self.0.state.lock().expect("semaphore lock");
+        waiters.permits += 1;
+        waiters.wake_next();
+    }
+}
+
+/// Async counterpart of `Client`, every call pops a client from the pool on a blocking
+/// thread of the runtime.  Cloning is cheap, clones share the pool and the limit.
+///
+/// With `tokio-runtime` the calls must be awaited within a Tokio runtime, spawning the
+/// blocking call panics anywhere else.  With `async-std-runtime` any executor works.
+///
+/// # Examples
+/// ```
+/// #[macro_use]
+/// extern crate bson;
+/// use mongo_leaf::prelude::*;
+/// use std::env;
+///
+/// # #[cfg(feature = "tokio-runtime")]
+/// # fn main() -> Result<()> {
+/// env::set_var("MONGODB_URI","mongodb://standard");
+/// let builder = Builder::new();
+/// let client = AsyncClient::new(builder.random_database_connect()?, 10);
+/// let mut runtime = tokio::runtime::Builder::new().basic_scheduler().build()?;
+///
+/// runtime.block_on(async {
+///     let collection = client.default_database().collection("test");
+///     collection.insert_many(vec![doc!{"name": "omg"}, doc!{"name": "foo"}]).await?;
+///
+///     let docs = collection.find(doc!{"name": "foo"}).await?;
+///     assert_eq!(1, docs.len());
+///
+///     let result = collection.delete(doc!{}).await?;
+///     assert_eq!(2, result.deleted);
+///
+///     # client.default_database().destroy().await?;
+///     Ok(())
+/// })
+/// # }
+/// # #[cfg(not(feature = "tokio-runtime"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone, Debug)]
+pub struct AsyncClient {
+    pool: Arc<ClientPoolc>,
+    permits: Arc<Semaphore>,
+}
+
+impl AsyncClient {
+    /// At most `max_blocking` calls run at once, keep it at or below the pool's
+    /// `maxPoolSize` so no blocking thread waits for a client.
+    pub fn new(pool: ClientPoolc, max_blocking: usize) -> Self {
+        AsyncClient {
+            pool: Arc::new(pool),
+            permits: Arc::new(Semaphore::new(max_blocking)),
+        }
+    }
+
+    /// Runs blocking calls with a client of the pool on a blocking thread.
+    pub async fn run<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&Clientc) -> Result<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let permit = self.permits.acquire().await;
+        let pool = self.pool.clone();
+
+        spawn_blocking(move || {
+            // Held until the call returns, also when the future was dropped meanwhile.
+            let _permit = permit;
+            f(&pool.pop())
+        })
+        .await
+    }
+
+    pub fn database(&self, name: impl Into<String>) -> AsyncDatabase {
+        AsyncDatabase {
+            client: self.clone(),
+            name: Some(name.into()),
+        }
+    }
+
+    /// The database given in the connection URI.
+    pub fn default_database(&self) -> AsyncDatabase {
+        AsyncDatabase {
+            client: self.clone(),
+            name: None,
+        }
+    }
+
+    pub async fn command_simple(
+        &self,
+        db_name: impl Into<String>,
+        command: Document,
+    ) -> Result<Document> {
+        let db_name = db_name.into();
+        self.run(move |client| client.command_simple(db_name, command, None))
+            .await
+    }
+
+    pub async fn list_database_names(&self, filter: Option<Document>) -> Result<Vec<String>> {
+        self.run(move |client| client.list_database_names(filter, None))
+            .await
+    }
+
+    /// Runs `f` in a transaction, committed when `f` succeeds and aborted otherwise.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # #[cfg(feature = "tokio-runtime")]
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://repl/?replicaSet=rs0");
+    /// let builder = Builder::new();
+    /// let client = AsyncClient::new(builder.random_database_connect()?, 10);
+    /// let mut runtime = tokio::runtime::Builder::new().basic_scheduler().build()?;
+    ///
+    /// runtime.block_on(client.with_transaction(|client, session| {
+    ///     let accounts = client.default_database().get_collection("accounts").with_session(session);
+    ///     accounts.update_one(doc!{"_id": "alice"}, doc!{"$inc": {"balance": -50}})?;
+    ///     accounts.update_one(doc!{"_id": "bob"}, doc!{"$inc": {"balance": 50}})?;
+    ///     Ok(())
+    /// }))?;
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "tokio-runtime"))]
+    /// # fn main() {}
+    /// ```
+    pub async fn with_transaction<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&Clientc, &Sessionc) -> Result<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        self.run(move |client| {
+            let session = client.start_session(None)?;
+            session.start_transaction(None)?;
+
+            match f(client, &session) {
+                Ok(result) => {
+                    session.commit()?;
+                    Ok(result)
+                }
+                Err(err) => {
+                    // The error of `f` is more useful than a failed abort.
+                    session.abort().ok();
+                    Err(err)
+                }
+            }
+        })
+        .await
+    }
+}
+
+/// Async counterpart of `Database`.
+#[derive(Clone, Debug)]
+pub struct AsyncDatabase {
+    client: AsyncClient,
+    /// `None` for the default database of the URI
+    name: Option<String>,
+}
+
+impl AsyncDatabase {
+    /// Runs blocking calls with the database on a blocking thread.
+    pub async fn run<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&Databasec) -> Result<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let name = self.name.clone();
+
+        self.client
+            .run(move |client| match name {
+                Some(name) => f(&client.get_database(name)),
+                None => f(&client.default_database()),
+            })
+            .await
+    }
+
+    pub fn collection(&self, name: impl Into<String>) -> AsyncCollection {
+        AsyncCollection {
+            database: self.clone(),
+            name: name.into(),
+        }
+    }
+
+    /// Runs a command, use `run` to pass `RunCommand` opts or a session.
+    pub async fn run_command(&self, command: Document) -> Result<Document> {
+        self.run(move |db| db.run_command(command, None)).await
+    }
+
+    pub async fn list_collection_names(&self, filter: Option<Document>) -> Result<Vec<String>> {
+        self.run(move |db| db.list_collection_names(filter)).await
+    }
+
+    pub async fn destroy(&self) -> Result<bool> {
+        self.run(|db| db.destroy()).await
+    }
+}
+
+/// Async counterpart of `Collection`, cursors are read to the end on the blocking thread.
+#[derive(Clone, Debug)]
+pub struct AsyncCollection {
+    database: AsyncDatabase,
+    name: String,
+}
+
+impl AsyncCollection {
+    /// Runs blocking calls with the collection on a blocking thread.
+    pub async fn run<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(&Collectionc) -> Result<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let name = self.name.clone();
+
+        self.database
+            .run(move |db| f(&db.get_collection(name)))
+            .await
+    }
+
+    pub async fn insert_one(&self, doc: Document) -> Result<InsertOneResult> {
+        self.insert_one_with_opts(doc, None).await
+    }
+
+    pub async fn insert_one_with_opts(
+        &self,
+        doc: Document,
+        opts: Option<Insert>,
+    ) -> Result<InsertOneResult> {
+        self.run(move |collection| collection.insert_one_with_opts(doc, opts))
+            .await
+    }
+
+    pub async fn insert_many(&self, docs: Vec<Document>) -> Result<InsertManyResult> {
+        self.insert_many_with_opts(docs, None).await
+    }
+
+    pub async fn insert_many_with_opts(
+        &self,
+        docs: Vec<Document>,
+        opts: Option<Insert>,
+    ) -> Result<InsertManyResult> {
+        self.run(move |collection| collection.insert_many_with_opts(docs, opts))
+            .await
+    }
+
+    pub async fn find(&self, filter: Document) -> Result<Vec<Document>> {
+        self.find_with_opts(filter, None).await
+    }
+
+    pub async fn find_with_opts(
+        &self,
+        filter: Document,
+        opts: Option<FindOptions>,
+    ) -> Result<Vec<Document>> {
+        self.run(move |collection| collection.find_with_opts(filter, opts).collect())
+            .await
+    }
+
+    pub async fn count_documents(
+        &self,
+        filter: Document,
+        opts: Option<CountDocuments>,
+    ) -> Result<i64> {
+        self.run(move |collection| collection.count_documents(filter, opts))
+            .await
+    }
+
+    pub async fn aggregate(&self, pipeline: impl Into<Pipeline>) -> Result<Vec<Document>> {
+        self.aggregate_with_opts(pipeline, None).await
+    }
+
+    pub async fn aggregate_with_opts(
+        &self,
+        pipeline: impl Into<Pipeline>,
+        opts: Option<Aggregate>,
+    ) -> Result<Vec<Document>> {
+        let pipeline = pipeline.into();
+        self.run(move |collection| collection.aggregate_with_opts(pipeline, opts).collect())
+            .await
+    }
+
+    pub async fn update(&self, selector: Document, update: Document) -> Result<UpdateResult> {
+        self.update_with_opts(selector, update, None).await
+    }
+
+    pub async fn update_with_opts(
+        &self,
+        selector: Document,
+        update: Document,
+        opts: Option<Update>,
+    ) -> Result<UpdateResult> {
+        self.run(move |collection| collection.update_with_opts(selector, update, opts))
+            .await
+    }
+
+    pub async fn update_one(&self, selector: Document, update: Document) -> Result<UpdateResult> {
+        self.update_one_with_opts(selector, update, None).await
+    }
+
+    pub async fn update_one_with_opts(
+        &self,
+        selector: Document,
+        update: Document,
+        opts: Option<UpdateOne>,
+    ) -> Result<UpdateResult> {
+        self.run(move |collection| collection.update_one_with_opts(selector, update, opts))
+            .await
+    }
+
+    pub async fn replace_one(
+        &self,
+        selector: Document,
+        replacement: Document,
+    ) -> Result<UpdateResult> {
+        self.replace_one_with_opts(selector, replacement, None)
+            .await
+    }
+
+    pub async fn replace_one_with_opts(
+        &self,
+        selector: Document,
+        replacement: Document,
+        opts: Option<ReplaceOne>,
+    ) -> Result<UpdateResult> {
+        self.run(move |collection| collection.replace_one_with_opts(selector, replacement, opts))
+            .await
+    }
+
+    pub async fn delete(&self, selector: Document) -> Result<DeleteResult> {
+        self.delete_with_opts(selector, None).await
+    }
+
+    pub async fn delete_with_opts(
+        &self,
+        selector: Document,
+        opts: Option<Remove>,
+    ) -> Result<DeleteResult> {
+        self.run(move |collection| collection.delete_with_opts(selector, opts))
+            .await
+    }
+
+    pub async fn delete_one(&self, selector: Document) -> Result<DeleteResult> {
+        self.delete_one_with_opts(selector, None).await
+    }
+
+    pub async fn delete_one_with_opts(
+        &self,
+        selector: Document,
+        opts: Option<RemoveOne>,
+    ) -> Result<DeleteResult> {
+        self.run(move |collection| collection.delete_one_with_opts(selector, opts))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Semaphore;
+    use futures::{
+        executor::block_on,
+        future::join,
+        task::{noop_waker, ArcWake},
+    };
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        task::{Context, Poll},
+    };
+
+    /// Records whether it was woken.
+    #[derive(Default)]
+    struct Woken(AtomicBool);
+
+    impl ArcWake for Woken {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_semaphore_waits_for_permit() {
+        let semaphore = Arc::new(Semaphore::new(1));
+
+        block_on(async {
+            let first = semaphore.acquire().await;
+            let waiting = async { semaphore.acquire().await };
+            let release = async move { drop(first) };
+
+            let (_second, _) = join(waiting, release).await;
+            assert_eq!(0, semaphore.state.lock().unwrap().permits);
+        });
+    }
+
+    #[test]
+    fn test_semaphore_forgets_cancelled_waiters() {
+        let semaphore = Arc::new(Semaphore::new(1));
+        let first = block_on(semaphore.acquire());
+
+        let noop = noop_waker();
+        let woken = Arc::new(Woken::default());
+        let woken_waker = futures::task::waker(woken.clone());
+
+        let mut cancelled = semaphore.acquire();
+        let mut waiting = semaphore.acquire();
+        for _ in 0..3 {
+            let polled = Pin::new(&mut cancelled).poll(&mut Context::from_waker(&noop));
+            assert!(polled.is_pending());
+        }
+        let polled = Pin::new(&mut waiting).poll(&mut Context::from_waker(&woken_waker));
+        assert!(polled.is_pending());
+        assert_eq!(2, semaphore.state.lock().unwrap().queue.len());
+
+        // The permit wakes the first waiter, which passes it on when dropped.
+        drop(first);
+        assert!(!woken.0.load(Ordering::SeqCst));
+        drop(cancelled);
+        assert!(woken.0.load(Ordering::SeqCst));
+
+        match Pin::new(&mut waiting).poll(&mut Context::from_waker(&woken_waker)) {
+            Poll::Ready(_permit) => assert!(semaphore.state.lock().unwrap().queue.is_empty()),
+            Poll::Pending => panic!("expected the permit"),
+        }
+    }
+}
@@ -0,0 +1,316 @@
+//! Batches of writes sent to the server with as few round trips as possible.
+
+use crate::{
+    bindings,
+    bsonc::Bsonc,
+    collection::ensure_id,
+    error::{BsoncError, BulkOperationError, Result, WriteError},
+    results::BulkWriteResult,
+};
+use bson::{Bson, Document};
+use std::collections::HashMap;
+use std::ptr;
+
+/// A single write queued in a bulk operation.
+pub enum WriteModel {
+    /// Insert a document, an ObjectId `_id` is generated when it has none
+    InsertOne { document: Document },
+    /// Update the first document matching the filter
+    UpdateOne {
+        filter: Document,
+        update: Document,
+        upsert: bool,
+    },
+    /// Update every document matching the filter
+    UpdateMany {
+        filter: Document,
+        update: Document,
+        upsert: bool,
+    },
+    /// Replace the first document matching the filter
+    ReplaceOne {
+        filter: Document,
+        replacement: Document,
+        upsert: bool,
+    },
+    /// Delete the first document matching the filter
+    DeleteOne { filter: Document },
+    /// Delete every document matching the filter
+    DeleteMany { filter: Document },
+}
+
+pub struct BulkWritec {
+    inner: *mut bindings::mongoc_bulk_operation_t,
+    /// Whether the bulk stops at the first failed write
+    ordered: bool,
+    len: usize,
+    inserted_ids: HashMap<usize, Bson>,
+}
+
+pub trait BulkWrite {
+    /// Queues a write, nothing is sent to the server until `execute` is called.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// let mut bulk = collection.bulk_write()?;
+    /// bulk.append(WriteModel::InsertOne { document: doc!{"name": "omg"} })?
+    ///     .append(WriteModel::UpdateOne {
+    ///         filter: doc!{"name": "omg"},
+    ///         update: doc!{"$set": {"name": "foo"}},
+    ///         upsert: false,
+    ///     })?;
+    /// let result = bulk.execute()?;
+    /// assert_eq!(1, result.inserted);
+    /// assert_eq!(1, result.modified);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn append(&mut self, model: WriteModel) -> Result<&mut Self>;
+
+    /// Sends every queued write to the server.  When any write fails the error is a
+    /// `BulkOperationError` holding the server reply, the failed writes and the result of
+    /// the writes applied before, with the `_id` of the inserted documents.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// let opts = BulkOperation { ordered: true, ..Default::default() };
+    /// let mut bulk = collection.bulk_write_with_opts(Some(opts))?;
+    /// bulk.append(WriteModel::InsertOne { document: doc!{"_id": 1} })?
+    ///     .append(WriteModel::InsertOne { document: doc!{"_id": 1} })?;
+    ///
+    /// let error = bulk.execute().unwrap_err();
+    /// let error = error.downcast_ref::<BulkOperationError>().unwrap();
+    /// assert_eq!(1, error.write_errors[0].index);
+    /// assert_eq!(Some(&bson::Bson::I32(1)), error.result.inserted_ids.get(&0));
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn execute(self) -> Result<BulkWriteResult>;
+}
+
+impl BulkWritec {
+    pub(crate) fn from_ptr(inner: *mut bindings::mongoc_bulk_operation_t, ordered: bool) -> Self {
+        assert!(!inner.is_null());
+        BulkWritec {
+            inner,
+            ordered,
+            len: 0,
+            inserted_ids: HashMap::new(),
+        }
+    }
+}
+
+impl BulkWrite for BulkWritec {
+    fn append(&mut self, model: WriteModel) -> Result<&mut Self> {
+        let mut error = BsoncError::empty();
+        let mut inserted_id = None;
+
+        let success = match model {
+            WriteModel::InsertOne { mut document } => {
+                inserted_id = Some(ensure_id(&mut document)?);
+
+                unsafe {
+                    bindings::mongoc_bulk_operation_insert_with_opts(
+                        self.inner,
+                        Bsonc::from_document(&document)?.as_ptr(),
+                        ptr::null(),
+                        error.as_mut_ptr(),
+                    )
+                }
+            }
+            WriteModel::UpdateOne {
+                filter,
+                update,
+                upsert,
+            } => unsafe {
+                bindings::mongoc_bulk_operation_update_one_with_opts(
+                    self.inner,
+                    Bsonc::from_document(&filter)?.as_ptr(),
+                    Bsonc::from_document(&update)?.as_ptr(),
+                    Bsonc::from_document(&doc! { "upsert": upsert })?.as_ptr(),
+                    error.as_mut_ptr(),
+                )
+            },
+            WriteModel::UpdateMany {
+                filter,
+                update,
+                upsert,
+            } => unsafe {
+                bindings::mongoc_bulk_operation_update_many_with_opts(
+                    self.inner,
+                    Bsonc::from_document(&filter)?.as_ptr(),
+                    Bsonc::from_document(&update)?.as_ptr(),
+                    Bsonc::from_document(&doc! { "upsert": upsert })?.as_ptr(),
+                    error.as_mut_ptr(),
+                )
+            },
+            WriteModel::ReplaceOne {
+                filter,
+                replacement,
+                upsert,
+            } => unsafe {
+                bindings::mongoc_bulk_operation_replace_one_with_opts(
+                    self.inner,
+                    Bsonc::from_document(&filter)?.as_ptr(),
+                    Bsonc::from_document(&replacement)?.as_ptr(),
+                    Bsonc::from_document(&doc! { "upsert": upsert })?.as_ptr(),
+                    error.as_mut_ptr(),
+                )
+            },
+            WriteModel::DeleteOne { filter } => unsafe {
+                bindings::mongoc_bulk_operation_remove_one_with_opts(
+                    self.inner,
+                    Bsonc::from_document(&filter)?.as_ptr(),
+                    ptr::null(),
+                    error.as_mut_ptr(),
+                )
+            },
+            WriteModel::DeleteMany { filter } => unsafe {
+                bindings::mongoc_bulk_operation_remove_many_with_opts(
+                    self.inner,
+                    Bsonc::from_document(&filter)?.as_ptr(),
+                    ptr::null(),
+                    error.as_mut_ptr(),
+                )
+            },
+        };
+
+        if success {
+            if let Some(id) = inserted_id {
+                self.inserted_ids.insert(self.len, id);
+            }
+            self.len += 1;
+            Ok(self)
+        } else {
+            Err(error.into())
+        }
+    }
+
+    fn execute(mut self) -> Result<BulkWriteResult> {
+        let mut error = BsoncError::empty();
+        let reply = Bsonc::empty();
+
+        let server_id = unsafe {
+            bindings::mongoc_bulk_operation_execute(
+                self.inner,
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        let reply = reply.as_document()?;
+
+        if server_id == 0 {
+            let mut error = BulkOperationError::new(error, reply);
+            error.result.inserted_ids = applied_inserts(
+                std::mem::take(&mut self.inserted_ids),
+                self.ordered,
+                error.result.inserted,
+                &error.write_errors,
+            );
+            Err(error.into())
+        } else {
+            let inserted_ids = std::mem::take(&mut self.inserted_ids);
+            Ok(BulkWriteResult::from_reply(&reply, inserted_ids))
+        }
+    }
+}
+
+/// The inserts among `inserted_ids` the server applied before a bulk failed.  An ordered
+/// bulk stops at its first failed write, so those are its first `inserted` inserts, an
+/// unordered one goes on and applies every insert but the failed ones.
+fn applied_inserts(
+    mut inserted_ids: HashMap<usize, Bson>,
+    ordered: bool,
+    inserted: i64,
+    write_errors: &[WriteError],
+) -> HashMap<usize, Bson> {
+    if ordered {
+        let mut indexes: Vec<usize> = inserted_ids.keys().cloned().collect();
+        indexes.sort();
+
+        if let Some(&first_skipped) = indexes.get(inserted.max(0) as usize) {
+            inserted_ids.retain(|&index, _| index < first_skipped);
+        }
+    } else {
+        inserted_ids.retain(|&index, _| write_errors.iter().all(|error| error.index != index));
+    }
+
+    inserted_ids
+}
+
+impl Drop for BulkWritec {
+    fn drop(&mut self) {
+        if !self.inner.is_null() {
+            unsafe {
+                bindings::mongoc_bulk_operation_destroy(self.inner);
+            }
+            self.inner = ptr::null_mut();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::applied_inserts;
+    use crate::error::WriteError;
+    use bson::Bson;
+    use std::collections::HashMap;
+
+    fn ids(indexes: &[usize]) -> HashMap<usize, Bson> {
+        indexes
+            .iter()
+            .map(|&index| (index, Bson::I32(index as i32)))
+            .collect()
+    }
+
+    fn duplicate_key(index: usize) -> WriteError {
+        WriteError {
+            index,
+            code: 11000,
+            message: "E11000 duplicate key error".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_applied_inserts_of_ordered_bulk() {
+        let applied = applied_inserts(ids(&[0, 2, 3, 5]), true, 2, &[duplicate_key(3)]);
+        assert_eq!(ids(&[0, 2]), applied);
+    }
+
+    #[test]
+    fn test_applied_inserts_of_unordered_bulk() {
+        let applied = applied_inserts(ids(&[0, 2, 3, 5]), false, 3, &[duplicate_key(3)]);
+        assert_eq!(ids(&[0, 2, 5]), applied);
+    }
+}
@@ -0,0 +1,293 @@
+//! Typed events of a change stream.
+
+use crate::{error::Result, options::Timestamp};
+use bson::{Bson, Document};
+
+/// Database and collection an event happened in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Namespace {
+    pub db: String,
+    /// Not set for events on the whole database
+    pub coll: Option<String>,
+}
+
+impl Namespace {
+    fn from_document(d: &Document) -> Self {
+        Namespace {
+            db: d.get_str("db").unwrap_or_default().to_string(),
+            coll: d.get_str("coll").ok().map(str::to_string),
+        }
+    }
+}
+
+/// An array that an update shortened.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TruncatedArray {
+    pub field: String,
+    pub new_size: i64,
+}
+
+/// Fields changed by an update.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateDescription {
+    /// New values of the set fields, by dotted path
+    pub updated_fields: Document,
+    pub removed_fields: Vec<String>,
+    pub truncated_arrays: Vec<TruncatedArray>,
+}
+
+impl UpdateDescription {
+    fn from_document(d: &Document) -> Self {
+        let removed_fields = match d.get_array("removedFields") {
+            Ok(fields) => fields
+                .iter()
+                .filter_map(|field| match field {
+                    Bson::String(field) => Some(field.clone()),
+                    _ => None,
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        let truncated_arrays = match d.get_array("truncatedArrays") {
+            Ok(arrays) => arrays
+                .iter()
+                .filter_map(|array| match array {
+                    Bson::Document(array) => Some(TruncatedArray {
+                        field: array.get_str("field").unwrap_or_default().to_string(),
+                        new_size: match array.get("newSize") {
+                            Some(Bson::I32(size)) => i64::from(*size),
+                            Some(Bson::I64(size)) => *size,
+                            _ => 0,
+                        },
+                    }),
+                    _ => None,
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        UpdateDescription {
+            updated_fields: d.get_document("updatedFields").cloned().unwrap_or_default(),
+            removed_fields,
+            truncated_arrays,
+        }
+    }
+}
+
+/// Fields shared by every kind of change event.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangeEventInfo {
+    /// The `_id` of the event, usable as `resume_after`
+    pub resume_token: Document,
+    pub namespace: Option<Namespace>,
+    /// Time of the oplog entry, usable as `start_at_operation_time`
+    pub cluster_time: Option<Timestamp>,
+    /// Transaction number, when the change was part of a transaction
+    pub txn_number: Option<i64>,
+    /// Session of the transaction
+    pub lsid: Option<Document>,
+    /// The document after the change, see `ChangeStreamOptions::full_document`
+    pub full_document: Option<Document>,
+    /// The document before the change, see `ChangeStreamOptions::full_document_before_change`
+    pub full_document_before_change: Option<Document>,
+}
+
+/// A change stream event by operation type.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChangeEvent {
+    Insert {
+        info: ChangeEventInfo,
+        document_key: Document,
+    },
+    Update {
+        info: ChangeEventInfo,
+        document_key: Document,
+        update_description: UpdateDescription,
+    },
+    Replace {
+        info: ChangeEventInfo,
+        document_key: Document,
+    },
+    Delete {
+        info: ChangeEventInfo,
+        document_key: Document,
+    },
+    Drop {
+        info: ChangeEventInfo,
+    },
+    Rename {
+        info: ChangeEventInfo,
+        to: Namespace,
+    },
+    DropDatabase {
+        info: ChangeEventInfo,
+    },
+    /// The stream can not continue, e.g. after its collection was dropped
+    Invalidate {
+        info: ChangeEventInfo,
+    },
+    /// Any operation type this crate does not know about
+    Other {
+        info: ChangeEventInfo,
+        operation_type: String,
+    },
+}
+
+impl ChangeEvent {
+    /// Decodes an event as returned by the change stream.
+    pub fn from_document(d: &Document) -> Result<Self> {
+        let info = ChangeEventInfo {
+            resume_token: d.get_document("_id")?.clone(),
+            namespace: d.get_document("ns").ok().map(Namespace::from_document),
+            cluster_time: d.get("clusterTime").and_then(Timestamp::from_bson),
+            txn_number: match d.get("txnNumber") {
+                Some(Bson::I64(number)) => Some(*number),
+                Some(Bson::I32(number)) => Some(i64::from(*number)),
+                _ => None,
+            },
+            lsid: d.get_document("lsid").ok().cloned(),
+            full_document: d.get_document("fullDocument").ok().cloned(),
+            full_document_before_change: d.get_document("fullDocumentBeforeChange").ok().cloned(),
+        };
+        let document_key = || d.get_document("documentKey").cloned().unwrap_or_default();
+
+        let event = match d.get_str("operationType")? {
+            "insert" => ChangeEvent::Insert {
+                info,
+                document_key: document_key(),
+            },
+            "update" => ChangeEvent::Update {
+                info,
+                document_key: document_key(),
+                update_description: UpdateDescription::from_document(
+                    d.get_document("updateDescription")?,
+                ),
+            },
+            "replace" => ChangeEvent::Replace {
+                info,
+                document_key: document_key(),
+            },
+            "delete" => ChangeEvent::Delete {
+                info,
+                document_key: document_key(),
+            },
+            "drop" => ChangeEvent::Drop { info },
+            "rename" => ChangeEvent::Rename {
+                info,
+                to: Namespace::from_document(d.get_document("to")?),
+            },
+            "dropDatabase" => ChangeEvent::DropDatabase { info },
+            "invalidate" => ChangeEvent::Invalidate { info },
+            other => ChangeEvent::Other {
+                info,
+                operation_type: other.to_string(),
+            },
+        };
+
+        Ok(event)
+    }
+
+    /// The fields shared by every kind of event.
+    pub fn info(&self) -> &ChangeEventInfo {
+        match *self {
+            ChangeEvent::Insert { ref info, .. }
+            | ChangeEvent::Update { ref info, .. }
+            | ChangeEvent::Replace { ref info, .. }
+            | ChangeEvent::Delete { ref info, .. }
+            | ChangeEvent::Drop { ref info }
+            | ChangeEvent::Rename { ref info, .. }
+            | ChangeEvent::DropDatabase { ref info }
+            | ChangeEvent::Invalidate { ref info }
+            | ChangeEvent::Other { ref info, .. } => info,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChangeEvent, Namespace, TruncatedArray};
+    use crate::options::Timestamp;
+    use bson::Bson;
+
+    #[test]
+    fn test_change_event_update() {
+        let event = ChangeEvent::from_document(&doc! {
+            "_id": { "_data": "8263" },
+            "operationType": "update",
+            "clusterTime": Bson::TimeStamp(1_600_000_000 << 32 | 7),
+            "txnNumber": 3i64,
+            "ns": { "db": "shop", "coll": "orders" },
+            "documentKey": { "_id": 1 },
+            "updateDescription": {
+                "updatedFields": { "status": "paid" },
+                "removedFields": ["draft"],
+                "truncatedArrays": [{ "field": "items", "newSize": 2 }],
+            },
+        })
+        .unwrap();
+
+        match event {
+            ChangeEvent::Update {
+                ref info,
+                ref document_key,
+                ref update_description,
+            } => {
+                assert_eq!(doc! { "_data": "8263" }, info.resume_token);
+                assert_eq!(
+                    Some(Timestamp {
+                        time: 1_600_000_000,
+                        increment: 7,
+                    }),
+                    info.cluster_time
+                );
+                assert_eq!(Some(3), info.txn_number);
+                assert_eq!(doc! { "_id": 1 }, *document_key);
+                assert_eq!(doc! { "status": "paid" }, update_description.updated_fields);
+                assert_eq!(vec!["draft".to_string()], update_description.removed_fields);
+                assert_eq!(
+                    vec![TruncatedArray {
+                        field: "items".to_string(),
+                        new_size: 2,
+                    }],
+                    update_description.truncated_arrays
+                );
+            }
+            _ => panic!("expected an update event, got {:?}", event),
+        }
+        assert_eq!(
+            Some(Namespace {
+                db: "shop".to_string(),
+                coll: Some("orders".to_string()),
+            }),
+            event.info().namespace
+        );
+    }
+
+    #[test]
+    fn test_change_event_rename_and_unknown() {
+        let event = ChangeEvent::from_document(&doc! {
+            "_id": { "_data": "8264" },
+            "operationType": "rename",
+            "ns": { "db": "shop", "coll": "orders" },
+            "to": { "db": "shop", "coll": "archive" },
+        })
+        .unwrap();
+        match event {
+            ChangeEvent::Rename { ref to, .. } => assert_eq!(Some("archive".to_string()), to.coll),
+            _ => panic!("expected a rename event, got {:?}", event),
+        }
+
+        let event = ChangeEvent::from_document(&doc! {
+            "_id": { "_data": "8265" },
+            "operationType": "createIndexes",
+        })
+        .unwrap();
+        match event {
+            ChangeEvent::Other {
+                ref operation_type, ..
+            } => assert_eq!("createIndexes", operation_type),
+            _ => panic!("expected an unknown event, got {:?}", event),
+        }
+    }
+}
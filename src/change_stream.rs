@@ -1,31 +1,91 @@
 use crate::{
     bindings,
     bsonc::Bsonc,
+    change_event::ChangeEvent,
     error::{BsoncError, Result},
+    worker::{Fetched, Worker},
 };
-use std::ptr;
+use bson::Document;
+use futures::stream::Stream;
+use std::{
+    pin::Pin,
+    ptr,
+    task::{Context, Poll},
+};
+
+/// An event with the resume token after it.
+type Event = (Document, Option<Document>);
 
 #[derive(Debug)]
 pub struct ChangeStreamc {
     inner: *mut bindings::mongoc_change_stream_t,
 }
 
-pub trait ChangeStream {}
+pub trait ChangeStream {
+    /// Token to resume the stream after the last returned event, pass it as `resume_after`
+    /// in the `ChangeStreamOptions` of a new stream.
+    fn resume_token(&self) -> Option<Document>;
+}
 
 impl ChangeStreamc {
     pub fn from_ptr(inner: *mut bindings::mongoc_change_stream_t) -> Self {
         ChangeStreamc { inner }
     }
 
+    /// Decodes the events of the stream as `ChangeEvent`s.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://repl/?replicaSet=rs0");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("changing");
+    /// let mut stream = collection.watch(None, None)?.typed();
+    ///
+    /// collection.insert_one(doc!{"_id": 1, "status": "new"})?;
+    /// collection.update_one(doc!{"_id": 1}, doc!{"$set": {"status": "paid"}})?;
+    ///
+    /// match stream.next().unwrap()? {
+    ///     ChangeEvent::Insert { document_key, .. } => assert_eq!(doc!{"_id": 1}, document_key),
+    ///     event => panic!("unexpected {:?}", event),
+    /// }
+    /// match stream.next().unwrap()? {
+    ///     ChangeEvent::Update { update_description, .. } => {
+    ///         assert_eq!(doc!{"status": "paid"}, update_description.updated_fields)
+    ///     }
+    ///     event => panic!("unexpected {:?}", event),
+    /// }
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn typed(self) -> TypedChangeStreamc<Self> {
+        TypedChangeStreamc { inner: self }
+    }
+
     pub fn get_error(&self) -> Option<BsoncError> {
         assert!(!self.inner.is_null(), "change stream ptr null");
 
+        ChangeStreamc::error_of(self.inner)
+    }
+
+    fn error_of(inner: *mut bindings::mongoc_change_stream_t) -> Option<BsoncError> {
         let mut error = BsoncError::empty();
         let reply = Bsonc::empty();
 
         let has_c_error = unsafe {
             bindings::mongoc_change_stream_error_document(
-                self.inner,
+                inner,
                 error.as_mut_ptr(),
                 &mut reply.as_ptr(),
             )
@@ -41,29 +101,197 @@ impl ChangeStreamc {
             Some(error)
         }
     }
-}
 
-impl ChangeStream for ChangeStreamc {}
-
-impl Iterator for ChangeStreamc {
-    type Item = Result<bson::Document>;
-
-    fn next(&mut self) -> Option<Self::Item> {
+    /// The next event, `None` when none arrived within `maxAwaitTimeMS`.
+    fn next_of(inner: *mut bindings::mongoc_change_stream_t) -> Option<Result<Document>> {
         let mut bson_ptr: *const bindings::bson_t = ptr::null_mut();
 
-        let success = unsafe { bindings::mongoc_change_stream_next(self.inner, &mut bson_ptr) };
+        let success = unsafe { bindings::mongoc_change_stream_next(inner, &mut bson_ptr) };
 
-        if let Some(err) = self.get_error() {
+        if let Some(err) = ChangeStreamc::error_of(inner) {
             Some(Err(err.into()))
         } else if success {
-            dbg!("success?");
             let bsonc = Bsonc::from_ptr(bson_ptr);
             Some(bsonc.as_document())
         } else {
-            dbg!("none?");
             None
         }
     }
+
+    fn resume_token_of(inner: *mut bindings::mongoc_change_stream_t) -> Option<Document> {
+        let token = unsafe { bindings::mongoc_change_stream_get_resume_token(inner) };
+        if token.is_null() {
+            return None;
+        }
+
+        // The token is owned by the stream, decode a copy.
+        Bsonc::from_ptr(unsafe { bindings::bson_copy(token) })
+            .as_document()
+            .ok()
+    }
+
+    /// The next event for a worker, `Empty` when none arrived within `maxAwaitTimeMS`.
+    fn fetch(&mut self) -> Fetched<Result<Event>> {
+        match ChangeStreamc::next_of(self.inner) {
+            Some(Ok(event)) => {
+                Fetched::Item(Ok((event, ChangeStreamc::resume_token_of(self.inner))))
+            }
+            Some(Err(err)) => Fetched::Item(Err(err)),
+            None => Fetched::Empty,
+        }
+    }
+}
+
+impl ChangeStream for ChangeStreamc {
+    fn resume_token(&self) -> Option<Document> {
+        ChangeStreamc::resume_token_of(self.inner)
+    }
+}
+
+/// Blocks until the next event, `None` when none arrived within `maxAwaitTimeMS`.  To wait
+/// for events in async code use the `watch` of `PooledClient`, `PooledDatabase` or
+/// `PooledCollection`, which runs the stream on a worker thread.
+impl Iterator for ChangeStreamc {
+    type Item = Result<bson::Document>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        ChangeStreamc::next_of(self.inner)
+    }
+}
+
+/// A change stream waiting for events on a worker thread, so polling never blocks the
+/// executor.  The thread owns the pooled client the stream was opened with, the client
+/// goes back to the pool after the stream is dropped, as soon as the thread's current
+/// wait returns.
+///
+/// The stream ends after the first error, which is also how an error opening the stream
+/// is returned.
+///
+/// # Examples
+/// ```no_run
+/// #[macro_use]
+/// extern crate bson;
+/// use futures::{executor::block_on, stream::StreamExt};
+/// use mongo_leaf::prelude::*;
+/// use std::{env, sync::Arc};
+///
+/// # fn main() -> Result<()> {
+/// env::set_var("MONGODB_URI","mongodb://repl/?replicaSet=rs0");
+/// let builder = Builder::new();
+/// let pool = Arc::new(builder.random_database_connect()?);
+/// let mut stream = pool.pop_pooled().default_database().collection("changing").watch(None, None);
+///
+/// pool.pop().default_database().get_collection("changing").insert_one(doc!{"_id": 1})?;
+///
+/// let event = block_on(StreamExt::next(&mut stream)).unwrap()?;
+/// assert_eq!(Ok("insert"), event.get_str("operationType"));
+/// assert!(stream.resume_token().is_some());
+///
+/// # pool.pop().default_database().destroy();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PooledChangeStream {
+    worker: Worker<Result<Event>>,
+    /// Resume token of the last event returned by the worker
+    resume_token: Option<Document>,
+}
+
+impl PooledChangeStream {
+    /// Opens the stream on the worker thread, `open` returns it along with the pooled
+    /// handle it was opened with, which is dropped after the stream.
+    pub(crate) fn open<H, O>(open: O) -> Self
+    where
+        O: FnOnce() -> Result<(ChangeStreamc, H)> + Send + 'static,
+    {
+        PooledChangeStream {
+            worker: Worker::open(open, |(stream, _)| stream.fetch()),
+            resume_token: None,
+        }
+    }
+
+    /// Decodes the events of the stream as `ChangeEvent`s, see `ChangeStreamc::typed`.
+    pub fn typed(self) -> TypedChangeStreamc<Self> {
+        TypedChangeStreamc { inner: self }
+    }
+
+    fn fetched(&mut self, fetched: Fetched<Result<Event>>) -> Option<Result<Document>> {
+        match fetched {
+            Fetched::Item(Ok((event, resume_token))) => {
+                self.resume_token = resume_token;
+                Some(Ok(event))
+            }
+            Fetched::Item(Err(err)) => Some(Err(err)),
+            Fetched::Empty | Fetched::Done => None,
+        }
+    }
+}
+
+impl ChangeStream for PooledChangeStream {
+    fn resume_token(&self) -> Option<Document> {
+        self.resume_token.clone()
+    }
+}
+
+/// Blocks until the next event, `None` when none arrived within `maxAwaitTimeMS`.
+impl Iterator for PooledChangeStream {
+    type Item = Result<bson::Document>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let fetched = self.worker.wait();
+        self.fetched(fetched)
+    }
+}
+
+impl Stream for PooledChangeStream {
+    type Item = Result<bson::Document>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            return match this.worker.poll(cx) {
+                Poll::Ready(Fetched::Empty) => continue,
+                Poll::Ready(fetched) => Poll::Ready(this.fetched(fetched)),
+                Poll::Pending => Poll::Pending,
+            };
+        }
+    }
+}
+
+/// Change stream yielding `ChangeEvent`s, see `ChangeStreamc::typed`.
+#[derive(Debug)]
+pub struct TypedChangeStreamc<S = ChangeStreamc> {
+    inner: S,
+}
+
+impl<S: ChangeStream> ChangeStream for TypedChangeStreamc<S> {
+    fn resume_token(&self) -> Option<Document> {
+        self.inner.resume_token()
+    }
+}
+
+impl<S: Iterator<Item = Result<Document>>> Iterator for TypedChangeStreamc<S> {
+    type Item = Result<ChangeEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|event| event.and_then(|event| ChangeEvent::from_document(&event)))
+    }
+}
+
+impl<S: Stream<Item = Result<Document>> + Unpin> Stream for TypedChangeStreamc<S> {
+    type Item = Result<ChangeEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.get_mut().inner)
+            .poll_next(cx)
+            .map(|event| {
+                event.map(|event| event.and_then(|event| ChangeEvent::from_document(&event)))
+            })
+    }
 }
 
 impl Drop for ChangeStreamc {
@@ -0,0 +1,234 @@
+//! Persisted resume tokens, so change stream consumers continue where they stopped.
+
+use crate::{
+    change_stream::ChangeStreamc,
+    collection::{Collection, Collectionc},
+    error::Result,
+    options::ReplaceOne,
+};
+use bson::Document;
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+/// Where the resume token of a change stream consumer is kept between runs.
+pub trait CheckpointStore {
+    /// The last saved token, `None` when nothing was saved yet.
+    fn load(&self) -> Result<Option<Document>>;
+    /// Saves the token, replacing the previous one.
+    fn save(&self, token: &Document) -> Result<()>;
+}
+
+/// Keeps the resume token as a BSON file.
+#[derive(Debug)]
+pub struct FileCheckpointStore {
+    path: PathBuf,
+}
+
+impl FileCheckpointStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileCheckpointStore { path: path.into() }
+    }
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    fn load(&self) -> Result<Option<Document>> {
+        match fs::read(&self.path) {
+            Ok(data) => Ok(Some(bson::decode_document(&mut data.as_slice())?)),
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Writes a temporary file next to the checkpoint and renames it, so a crash never
+    /// leaves a partially written token behind.
+    fn save(&self, token: &Document) -> Result<()> {
+        let mut data = Vec::new();
+        bson::encode_document(&mut data, token)?;
+
+        let tmp_path = self.path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(&data)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &self.path)?;
+
+        Ok(())
+    }
+}
+
+/// Keeps the resume token in a document of a MongoDB collection, one document per consumer.
+#[derive(Debug)]
+pub struct CollectionCheckpointStore {
+    collection: Collectionc,
+    id: String,
+}
+
+impl CollectionCheckpointStore {
+    /// Stores the token in the document with `_id` set to `id`.
+    pub fn new(collection: Collectionc, id: impl Into<String>) -> Self {
+        CollectionCheckpointStore {
+            collection,
+            id: id.into(),
+        }
+    }
+}
+
+impl CheckpointStore for CollectionCheckpointStore {
+    fn load(&self) -> Result<Option<Document>> {
+        match self.collection.find(doc! { "_id": self.id.clone() }).next() {
+            Some(checkpoint) => Ok(checkpoint?.get_document("token").ok().cloned()),
+            None => Ok(None),
+        }
+    }
+
+    fn save(&self, token: &Document) -> Result<()> {
+        let opts = ReplaceOne {
+            upsert: true,
+            ..Default::default()
+        };
+
+        self.collection.replace_one_with_opts(
+            doc! { "_id": self.id.clone() },
+            doc! { "_id": self.id.clone(), "token": token.clone() },
+            Some(opts),
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Runs a change stream from the last checkpoint and saves the resume token as events
+/// are processed.
+///
+/// Events are delivered at least once: after a crash the events processed since the
+/// last save are delivered again, saving after every event keeps that to one event.
+#[derive(Debug)]
+pub struct ResumableWatcher<S: CheckpointStore> {
+    store: S,
+    save_every: usize,
+}
+
+impl<S: CheckpointStore> ResumableWatcher<S> {
+    /// Watcher saving the resume token after every event.
+    pub fn new(store: S) -> Self {
+        ResumableWatcher {
+            store,
+            save_every: 1,
+        }
+    }
+
+    /// Saves the resume token after every `events` events instead, and whenever the
+    /// stream is idle.
+    pub fn save_every(mut self, events: usize) -> Self {
+        self.save_every = events.max(1);
+        self
+    }
+
+    /// Opens the stream with the saved token, `None` on the first run, and passes each
+    /// event to the handler until it returns false or an error.  The token of the last
+    /// handled event is saved before returning.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://repl/?replicaSet=rs0");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let orders = db.get_collection("orders");
+    /// let store = CollectionCheckpointStore::new(db.get_collection("checkpoints"), "billing");
+    ///
+    /// ResumableWatcher::new(store).save_every(10).run(
+    ///     |token| {
+    ///         let opts = ChangeStreamOptions { resume_after: token, ..Default::default() };
+    ///         orders.watch(None, Some(opts))
+    ///     },
+    ///     |event| {
+    ///         println!("{}", event);
+    ///         Ok(true)
+    ///     },
+    /// )?;
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn run<O, H>(&self, open: O, mut handler: H) -> Result<()>
+    where
+        O: FnOnce(Option<Document>) -> Result<ChangeStreamc>,
+        H: FnMut(&Document) -> Result<bool>,
+    {
+        let mut stream = open(self.store.load()?)?;
+        // Token of the last handled event, the stream's own token may already point past
+        // events the handler has not seen.
+        let mut pending = None;
+        let mut unsaved = 0;
+
+        loop {
+            match stream.next() {
+                Some(Ok(event)) => {
+                    let more = match handler(&event) {
+                        Ok(more) => more,
+                        Err(err) => {
+                            self.flush(&mut pending)?;
+                            return Err(err);
+                        }
+                    };
+
+                    pending = event.get_document("_id").ok().cloned();
+                    unsaved += 1;
+                    if unsaved >= self.save_every || !more {
+                        self.flush(&mut pending)?;
+                        unsaved = 0;
+                    }
+                    if !more {
+                        return Ok(());
+                    }
+                }
+                Some(Err(err)) => {
+                    self.flush(&mut pending)?;
+                    return Err(err);
+                }
+                None => {
+                    self.flush(&mut pending)?;
+                    unsaved = 0;
+                }
+            }
+        }
+    }
+
+    fn flush(&self, pending: &mut Option<Document>) -> Result<()> {
+        match pending.take() {
+            Some(token) => self.store.save(&token),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CheckpointStore, FileCheckpointStore};
+    use std::{env, fs};
+
+    #[test]
+    fn test_file_checkpoint_store() {
+        let path = env::temp_dir().join(format!("mongo_leaf_checkpoint_{}", std::process::id()));
+        let store = FileCheckpointStore::new(&path);
+        assert_eq!(None, store.load().unwrap());
+
+        store.save(&doc! { "_data": "first" }).unwrap();
+        store.save(&doc! { "_data": "second" }).unwrap();
+        assert_eq!(Some(doc! { "_data": "second" }), store.load().unwrap());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,21 +1,27 @@
 use crate::{
     bindings,
     bsonc::Bsonc,
-    client_pool::{ClientPool, ClientPoolc},
+    change_stream::{ChangeStream, ChangeStreamc},
+    client_pool::{ClientPoolc, PoolHandle},
     collection::{Collection, Collectionc},
     cursor::{Cursor, Cursorc},
     database::{Database, Databasec},
+    database_spec::DatabaseSpec,
     error::{BsoncError, Result},
+    options::{ChangeStreamOptions, ListDatabases},
+    read_concern::{ReadConcern, ReadConcernc},
     read_prefs::{ReadPrefs, ReadPrefsc},
     session::{Session, Sessionc},
     session_opts::{SessionOpts, SessionOptsc},
+    write_concern::{WriteConcern, WriteConcernc},
 };
 use std::ffi::CString;
 use std::ptr;
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct Clientc<'a> {
-    pub client_pool: &'a ClientPoolc,
+    pub client_pool: PoolHandle<'a>,
     inner: *mut bindings::mongoc_client_t,
 }
 
@@ -26,6 +32,7 @@ pub trait Client {
     type Database: Database;
     type Session: Session;
     type SessionOpts: SessionOpts + Sized + Default;
+    type ChangeStream: ChangeStream;
 
     fn as_mut_ptr(&self) -> *mut bindings::mongoc_client_t {
         ptr::null_mut()
@@ -56,11 +63,50 @@ pub trait Client {
     ) -> Self::Collection;
 
     fn start_session(&self, opts: Option<Self::SessionOpts>) -> Result<Self::Session>;
+
+    fn list_databases(
+        &self,
+        filter: Option<bson::Document>,
+        opts: Option<ListDatabases>,
+    ) -> Result<Vec<DatabaseSpec>>;
+    fn list_database_names(
+        &self,
+        filter: Option<bson::Document>,
+        opts: Option<ListDatabases>,
+    ) -> Result<Vec<String>>;
+
+    fn read_prefs(&self) -> ReadPrefsc;
+    fn set_read_prefs(&self, read_prefs: &ReadPrefsc);
+    fn read_concern(&self) -> ReadConcernc;
+    fn set_read_concern(&self, read_concern: &ReadConcernc);
+    fn write_concern(&self) -> WriteConcernc;
+    fn set_write_concern(&self, write_concern: &WriteConcernc);
+
+    fn watch(
+        &self,
+        pipeline: Option<bson::Document>,
+        opts: Option<ChangeStreamOptions>,
+    ) -> Result<Self::ChangeStream>;
 }
 
 impl<'a> Clientc<'a> {
     pub fn new(client_pool: &'a ClientPoolc, inner: *mut bindings::mongoc_client_t) -> Self {
-        Clientc { client_pool, inner }
+        Clientc {
+            client_pool: PoolHandle::Borrowed(client_pool),
+            inner,
+        }
+    }
+}
+
+impl Clientc<'static> {
+    pub(crate) fn from_shared(
+        client_pool: Arc<ClientPoolc>,
+        inner: *mut bindings::mongoc_client_t,
+    ) -> Self {
+        Clientc {
+            client_pool: PoolHandle::Shared(client_pool),
+            inner,
+        }
     }
 }
 
@@ -71,6 +117,7 @@ impl Client for Clientc<'_> {
     type Database = Databasec;
     type Session = Sessionc;
     type SessionOpts = SessionOptsc;
+    type ChangeStream = ChangeStreamc;
 
     fn as_mut_ptr(&self) -> *mut bindings::mongoc_client_t {
         self.inner
@@ -232,14 +279,14 @@ impl Client for Clientc<'_> {
             let db_cstring = CString::new(db_name.into()).expect("Valid database name");
             let ptr = bindings::mongoc_client_get_database(self.inner, db_cstring.as_ptr());
 
-            Databasec::new(ptr)
+            Databasec::new(self.inner, ptr)
         }
     }
 
     fn default_database(&self) -> Self::Database {
         unsafe {
             let ptr = bindings::mongoc_client_get_default_database(self.inner);
-            Databasec::new(ptr)
+            Databasec::new(self.inner, ptr)
         }
     }
 
@@ -267,7 +314,7 @@ impl Client for Clientc<'_> {
     /// let reply = session.start_transaction(None)?;
     /// assert!(reply);
     ///
-    /// let collection = db.get_collection("with_txn");
+    /// let collection = db.get_collection("with_txn").with_session(&session);
     ///
     /// let reply = collection.insert_many(vec![
     ///     doc!{"name": "first"},
@@ -282,7 +329,7 @@ impl Client for Clientc<'_> {
     /// let reply = session.abort()?;
     /// assert!(reply);
     ///
-    /// let count = collection.count(None)?;
+    /// let count = db.get_collection("with_txn").count(None)?;
     /// assert_eq!(0, count, "Transaction Aborted");
     ///
     /// # db.destroy();
@@ -305,12 +352,165 @@ impl Client for Clientc<'_> {
             Err(error.into())
         }
     }
+
+    /// Lists the databases on the server, optionally only those matching the filter, e.g.
+    /// `{"name": {"$regex": "^tenant_"}}` or `{"empty": true}`.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// db.get_collection("test").insert_one(doc!{"name": "omg"})?;
+    ///
+    /// let dbs = client.list_databases(Some(doc!{"name": db.name()}), None)?;
+    /// assert_eq!(1, dbs.len());
+    /// assert!(!dbs[0].empty);
+    /// assert!(dbs[0].size_on_disk > 0);
+    ///
+    /// let names = client.list_database_names(None, None)?;
+    /// assert!(names.contains(&db.name()));
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn list_databases(
+        &self,
+        filter: Option<bson::Document>,
+        opts: Option<ListDatabases>,
+    ) -> Result<Vec<DatabaseSpec>> {
+        let command = opts.unwrap_or_default().to_command(filter);
+        let reply = self.command_simple("admin", command, None)?;
+
+        Ok(reply
+            .get_array("databases")?
+            .iter()
+            .filter_map(|database| match database {
+                bson::Bson::Document(database) => Some(DatabaseSpec::from_document(database)),
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Lists the names of the databases on the server.
+    fn list_database_names(
+        &self,
+        filter: Option<bson::Document>,
+        opts: Option<ListDatabases>,
+    ) -> Result<Vec<String>> {
+        let opts = ListDatabases {
+            name_only: true,
+            ..opts.unwrap_or_default()
+        };
+
+        Ok(self
+            .list_databases(filter, Some(opts))?
+            .into_iter()
+            .map(|database| database.name)
+            .collect())
+    }
+
+    /// The read prefs used by operations on the client and its databases that don't set their own.
+    fn read_prefs(&self) -> ReadPrefsc {
+        ReadPrefsc::copy_from_ptr(unsafe { bindings::mongoc_client_get_read_prefs(self.inner) })
+    }
+
+    /// Sets the read prefs inherited by operations on the client and its databases.
+    /// The pool restores the read prefs of its URI when the client is pushed back.
+    fn set_read_prefs(&self, read_prefs: &ReadPrefsc) {
+        unsafe { bindings::mongoc_client_set_read_prefs(self.inner, read_prefs.as_ptr()) }
+    }
+
+    /// The read concern used by operations on the client and its databases that don't set their own.
+    fn read_concern(&self) -> ReadConcernc {
+        ReadConcernc::copy_from_ptr(unsafe { bindings::mongoc_client_get_read_concern(self.inner) })
+    }
+
+    /// Sets the read concern inherited by operations on the client and its databases.
+    /// The pool restores the read concern of its URI when the client is pushed back.
+    fn set_read_concern(&self, read_concern: &ReadConcernc) {
+        unsafe { bindings::mongoc_client_set_read_concern(self.inner, read_concern.as_ptr()) }
+    }
+
+    /// The write concern used by operations on the client and its databases that don't set their own.
+    fn write_concern(&self) -> WriteConcernc {
+        WriteConcernc::copy_from_ptr(unsafe {
+            bindings::mongoc_client_get_write_concern(self.inner)
+        })
+    }
+
+    /// Sets the write concern inherited by operations on the client and its databases.
+    /// The pool restores the write concern of its URI when the client is pushed back.
+    fn set_write_concern(&self, write_concern: &WriteConcernc) {
+        unsafe { bindings::mongoc_client_set_write_concern(self.inner, write_concern.as_ptr()) }
+    }
+
+    /// Watches every database and collection of the deployment with a single change stream.
+    ///
+    /// NOTE:  Change streams need a replica set or a sharded cluster.
+    /// # Examples
+    /// ```no_run
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://repl/?replicaSet=rs0");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let mut stream = client.watch(None, None)?;
+    ///
+    /// let db = client.default_database();
+    /// db.get_collection("audit").insert_one(doc!{"name": "first"})?;
+    ///
+    /// let change = stream.next().unwrap()?;
+    /// assert_eq!(Ok(db.name().as_str()), change.get_document("ns")?.get_str("db"));
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn watch(
+        &self,
+        pipeline: Option<bson::Document>,
+        opts: Option<ChangeStreamOptions>,
+    ) -> Result<Self::ChangeStream> {
+        let bson_pipeline = pipeline.map_or_else(Bsonc::empty, |o| {
+            Bsonc::from_document(&o).expect("should be valid")
+        });
+        let bsonc_opts = opts.unwrap_or_default().into_mongoc()?;
+
+        let inner = unsafe {
+            bindings::mongoc_client_watch(self.inner, bson_pipeline.as_ptr(), bsonc_opts.as_ptr())
+        };
+
+        let change_stream = ChangeStreamc::from_ptr(inner);
+
+        if let Some(error) = change_stream.get_error() {
+            return Err(error.into());
+        }
+
+        Ok(change_stream)
+    }
 }
 
 impl<'a> Drop for Clientc<'a> {
     fn drop(&mut self) {
         if !self.inner.is_null() {
-            self.client_pool.push(self);
+            self.client_pool.push_ptr(self.inner);
             self.destroy();
         }
     }
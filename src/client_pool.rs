@@ -1,21 +1,67 @@
 use crate::{
     bindings,
     client::{Client, Clientc},
-    error::Result,
+    error::{PoolExhaustedError, Result},
+    pooled::PooledClient,
     ssl_options::SSLOptions,
     uri::{Uri, Uric},
 };
-use std::ptr;
+use std::{
+    ops::Deref,
+    os::raw::c_char,
+    ptr,
+    sync::{Arc, Condvar, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Default of the URI option maxPoolSize.
+const DEFAULT_MAX_POOL_SIZE: i32 = 100;
 
-#[derive(Eq, Debug)]
+#[derive(Debug)]
 pub struct ClientPoolc {
     uri: Uric,
     inner: *mut bindings::mongoc_client_pool_t,
+    max_size: usize,
+    counts: Mutex<PoolStats>,
+    /// Signalled whenever a client is pushed back
+    returned: Condvar,
 }
 
 unsafe impl Send for ClientPoolc {}
 unsafe impl Sync for ClientPoolc {}
 
+/// Clients of a pool, as counted by this crate.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PoolStats {
+    /// Clients popped and not pushed back yet
+    pub checked_out: usize,
+    /// Clients pushed back and not popped again, an upper bound of the clients waiting in
+    /// the pool: with the URI option minPoolSize set, mongoc destroys pushed clients beyond
+    /// that many instead of keeping them
+    pub idle: usize,
+    /// The URI option maxPoolSize, `pop` blocks once this many clients are checked out
+    pub max: usize,
+}
+
+/// The pool a client goes back to when dropped.
+#[derive(Debug)]
+pub enum PoolHandle<'a> {
+    Borrowed(&'a ClientPoolc),
+    /// Keeps the pool alive as long as the client, see `ClientPoolc::pop_pooled`
+    Shared(Arc<ClientPoolc>),
+}
+
+impl Deref for PoolHandle<'_> {
+    type Target = ClientPoolc;
+
+    fn deref(&self) -> &ClientPoolc {
+        match *self {
+            PoolHandle::Borrowed(pool) => pool,
+            PoolHandle::Shared(ref pool) => pool,
+        }
+    }
+}
+
 pub trait ClientPool<'a> {
     type Pool: ClientPool<'a> + Sized;
     type Uri: Uri + Sized;
@@ -23,7 +69,10 @@ pub trait ClientPool<'a> {
 
     fn destroy(&mut self);
     fn pop(&'a self) -> Self::Client;
+    fn try_pop(&'a self) -> Option<Self::Client>;
+    fn pop_timeout(&'a self, timeout: Duration) -> Result<Self::Client>;
     fn push(&self, client: &mut Self::Client);
+    fn stats(&self) -> PoolStats;
 }
 
 impl ClientPoolc {
@@ -55,9 +104,132 @@ impl ClientPoolc {
             }
 
             assert!(!inner.is_null());
-            Ok(ClientPoolc { uri, inner })
+
+            let max_size = bindings::mongoc_uri_get_option_as_int32(
+                uri.as_mut_ptr(),
+                bindings::MONGOC_URI_MAXPOOLSIZE.as_ptr() as *const c_char,
+                DEFAULT_MAX_POOL_SIZE,
+            );
+
+            Ok(ClientPoolc {
+                uri,
+                inner,
+                max_size: max_size.max(0) as usize,
+                counts: Mutex::new(PoolStats::default()),
+                returned: Condvar::new(),
+            })
         }
     }
+
+    /// Retrieve a client like `pop` which keeps the pool alive until it is dropped and
+    /// returned to the pool.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mongo_leaf::prelude::*;
+    /// use std::{sync::Arc, thread};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let builder = Builder::new();
+    /// let pool = Arc::new(builder.connect()?);
+    /// let client = pool.pop_pooled();
+    ///
+    /// let handle = thread::spawn(move || {
+    ///     client.run(|client| client.get_database("db").list_collection_names(None))
+    /// });
+    /// handle.join().unwrap()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pop_pooled(self: &Arc<Self>) -> PooledClient {
+        PooledClient::new(Clientc::from_shared(self.clone(), self.pop_ptr()))
+    }
+
+    fn pop_ptr(&self) -> *mut bindings::mongoc_client_t {
+        // Not holding the lock here, the pop may wait for a push which needs it.
+        let client = unsafe { bindings::mongoc_client_pool_pop(self.inner) };
+        Self::popped(&mut self.counts.lock().expect("pool lock"));
+        client
+    }
+
+    fn try_pop_ptr(&self) -> Option<*mut bindings::mongoc_client_t> {
+        let mut counts = self.counts.lock().expect("pool lock");
+        let client = unsafe { bindings::mongoc_client_pool_try_pop(self.inner) };
+
+        if client.is_null() {
+            None
+        } else {
+            Self::popped(&mut counts);
+            Some(client)
+        }
+    }
+
+    fn pop_timeout_ptr(&self, timeout: Duration) -> Result<*mut bindings::mongoc_client_t> {
+        let deadline = Instant::now() + timeout;
+        let mut counts = self.counts.lock().expect("pool lock");
+
+        loop {
+            let client = unsafe { bindings::mongoc_client_pool_try_pop(self.inner) };
+            if !client.is_null() {
+                Self::popped(&mut counts);
+                return Ok(client);
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(PoolExhaustedError {
+                    timeout,
+                    max_size: self.max_size,
+                }
+                .into());
+            }
+
+            // Pushes take the lock, so none can slip in between the failed pop and the wait.
+            counts = self
+                .returned
+                .wait_timeout(counts, deadline - now)
+                .expect("pool lock")
+                .0;
+        }
+    }
+
+    /// Returns a client to the pool, used by `push` and when a client is dropped.
+    pub(crate) fn push_ptr(&self, client: *mut bindings::mongoc_client_t) {
+        // Defaults set on the client would otherwise stick to it and apply to whoever
+        // pops it next.
+        unsafe {
+            let uri = self.uri.as_mut_ptr();
+            bindings::mongoc_client_set_read_prefs(
+                client,
+                bindings::mongoc_uri_get_read_prefs_t(uri),
+            );
+            bindings::mongoc_client_set_read_concern(
+                client,
+                bindings::mongoc_uri_get_read_concern(uri),
+            );
+            bindings::mongoc_client_set_write_concern(
+                client,
+                bindings::mongoc_uri_get_write_concern(uri),
+            );
+        }
+
+        let mut counts = self.counts.lock().expect("pool lock");
+        unsafe {
+            bindings::mongoc_client_pool_push(self.inner, client);
+        }
+        counts.checked_out = counts.checked_out.saturating_sub(1);
+        counts.idle += 1;
+        // A woken `pop_timeout` may find the client taken by a blocking `pop` and go back
+        // to waiting, so every waiter gets a chance at it.
+        self.returned.notify_all();
+    }
+
+    /// The pool hands out idle clients before creating new ones.
+    fn popped(counts: &mut PoolStats) {
+        counts.checked_out += 1;
+        counts.idle = counts.idle.saturating_sub(1);
+    }
 }
 
 impl<'a> ClientPool<'a> for ClientPoolc {
@@ -114,9 +286,55 @@ impl<'a> ClientPool<'a> for ClientPoolc {
     /// # }
     /// ```
     fn pop(&'a self) -> Self::Client {
-        let clientc = unsafe { bindings::mongoc_client_pool_pop(self.inner) };
+        Clientc::new(self, self.pop_ptr())
+    }
 
-        Clientc::new(self, clientc)
+    /// From MongoC documentation:
+    ///
+    /// This function is identical to mongoc_client_pool_pop() except it will return NULL instead
+    /// of blocking for a client to become available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mongo_leaf::prelude::*;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let builder = Builder::new();
+    /// let pool = builder.connect()?;
+    /// match pool.try_pop() {
+    ///     Some(client) => assert_eq!(*client.client_pool, pool),
+    ///     None => println!("all {} clients are in use", pool.stats().max),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn try_pop(&'a self) -> Option<Self::Client> {
+        self.try_pop_ptr()
+            .map(|clientc| Clientc::new(self, clientc))
+    }
+
+    /// Retrieve a client, waiting at most `timeout` for one to be pushed back when all are
+    /// in use.  Fails with `PoolExhaustedError` after the timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mongo_leaf::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let builder = Builder::new();
+    /// let pool = builder.connect()?;
+    /// match pool.pop_timeout(Duration::from_millis(100)) {
+    ///     Ok(client) => assert_eq!(*client.client_pool, pool),
+    ///     Err(err) => assert!(err.downcast_ref::<PoolExhaustedError>().is_some()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn pop_timeout(&'a self, timeout: Duration) -> Result<Self::Client> {
+        Ok(Clientc::new(self, self.pop_timeout_ptr(timeout)?))
     }
 
     /// From MongoC documentation:
@@ -139,12 +357,36 @@ impl<'a> ClientPool<'a> for ClientPoolc {
     /// ```
     fn push(&self, client: &mut Self::Client) {
         if !client.as_mut_ptr().is_null() {
-            unsafe {
-                bindings::mongoc_client_pool_push(self.inner, client.as_mut_ptr());
-            }
+            self.push_ptr(client.as_mut_ptr());
             client.destroy();
         }
     }
+
+    /// Number of checked out and idle clients and the maximum size of the pool, see
+    /// `PoolStats::idle` for what idle counts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mongo_leaf::prelude::*;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let builder = Builder::new();
+    /// let pool = builder.connect()?;
+    /// let client = pool.pop();
+    /// assert_eq!(1, pool.stats().checked_out);
+    /// drop(client);
+    /// assert_eq!(0, pool.stats().checked_out);
+    /// assert_eq!(1, pool.stats().idle);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn stats(&self) -> PoolStats {
+        PoolStats {
+            max: self.max_size,
+            ..*self.counts.lock().expect("pool lock")
+        }
+    }
 }
 
 impl Drop for ClientPoolc {
@@ -158,3 +400,5 @@ impl PartialEq for ClientPoolc {
         self.uri.eq(&other.uri)
     }
 }
+
+impl Eq for ClientPoolc {}
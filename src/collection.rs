@@ -1,24 +1,44 @@
 use crate::{
     bindings,
     bsonc::Bsonc,
+    bulk_write::{BulkWrite, BulkWritec},
     change_stream::{ChangeStream, ChangeStreamc},
     cursor::{Cursor, Cursorc},
-    error::{BsoncError, Result},
+    error::{BsoncError, InvalidParamsError, MongoError, Result},
+    explain::{ExplainResult, ExplainVerbosity},
+    find_and_modify_opts::FindAndModifyOptsc,
     flags::FlagsValue,
-    options::{Aggregate, Count, FindAndModify, Insert, Remove, Update},
+    index_model::IndexModel,
+    options::{
+        Aggregate, BulkOperation, ChangeStreamOptions, Count, CountDocuments, Distinct,
+        EstimatedDocumentCount, FindAndModify, FindOptions, Insert, Remove, RemoveOne, ReplaceOne,
+        Tail, Update, UpdateOne,
+    },
+    pipeline::Pipeline,
+    read_concern::{ReadConcern, ReadConcernc},
     read_prefs::{ReadPrefs, ReadPrefsc},
+    results::{DeleteResult, InsertManyResult, InsertOneResult, UpdateResult},
+    session::{Session, Sessionc},
+    session_collection::SessionCollection,
+    tail_cursor::{TailCursor, TailCursorc},
+    write_concern::{WriteConcern, WriteConcernc},
 };
 
+use bson::{oid::ObjectId, Bson};
+use std::ffi::{CStr, CString};
 use std::ptr;
 
 #[derive(Debug)]
 pub struct Collectionc {
     inner: *mut bindings::mongoc_collection_t,
+    session: *mut bindings::mongoc_client_session_t,
 }
 
 pub trait Collection {
     type Cursor: Cursor;
     type ChangeStream: ChangeStream;
+    type BulkWrite: BulkWrite;
+    type TailCursor: TailCursor;
 
     /// Counts the number of documents in a collection.
     ///
@@ -61,68 +81,320 @@ pub trait Collection {
     fn count(&self, filter: Option<bson::Document>) -> Result<i64>;
     fn count_with_opts(&self, filter: Option<bson::Document>, opts: Option<Count>) -> Result<i64>;
 
-    fn insert_one(&self, doc: bson::Document) -> Result<bson::Document>;
+    fn count_documents(&self, filter: bson::Document, opts: Option<CountDocuments>) -> Result<i64>;
+    fn estimated_document_count(&self, opts: Option<EstimatedDocumentCount>) -> Result<i64>;
+    fn distinct(
+        &self,
+        field: &str,
+        filter: Option<bson::Document>,
+        opts: Option<Distinct>,
+    ) -> Result<Vec<Bson>>;
+
+    fn insert_one(&self, doc: bson::Document) -> Result<InsertOneResult>;
 
     fn insert_one_with_opts(
         &self,
         doc: bson::Document,
         opts: Option<Insert>,
-    ) -> Result<bson::Document>;
+    ) -> Result<InsertOneResult>;
 
-    fn insert_many(&self, docs: Vec<bson::Document>) -> Result<bson::Document>;
+    fn insert_many(&self, docs: Vec<bson::Document>) -> Result<InsertManyResult>;
 
     fn insert_many_with_opts(
         &self,
         docs: Vec<bson::Document>,
         opts: Option<Insert>,
-    ) -> Result<bson::Document>;
+    ) -> Result<InsertManyResult>;
 
-    fn delete(&self, selector: bson::Document) -> Result<bson::Document>;
+    fn delete(&self, selector: bson::Document) -> Result<DeleteResult>;
 
     fn delete_with_opts(
         &self,
         selector: bson::Document,
         opts: Option<Remove>,
-    ) -> Result<bson::Document>;
+    ) -> Result<DeleteResult>;
 
-    fn update(&self, selector: bson::Document, update: bson::Document) -> Result<bson::Document>;
+    fn update(&self, selector: bson::Document, update: bson::Document) -> Result<UpdateResult>;
 
     fn update_with_opts(
         &self,
         selector: bson::Document,
         update: bson::Document,
         opts: Option<Update>,
-    ) -> Result<bson::Document>;
+    ) -> Result<UpdateResult>;
+
+    fn update_one(&self, selector: bson::Document, update: bson::Document) -> Result<UpdateResult>;
+
+    fn update_one_with_opts(
+        &self,
+        selector: bson::Document,
+        update: bson::Document,
+        opts: Option<UpdateOne>,
+    ) -> Result<UpdateResult>;
+
+    fn replace_one(
+        &self,
+        selector: bson::Document,
+        replacement: bson::Document,
+    ) -> Result<UpdateResult>;
+
+    fn replace_one_with_opts(
+        &self,
+        selector: bson::Document,
+        replacement: bson::Document,
+        opts: Option<ReplaceOne>,
+    ) -> Result<UpdateResult>;
+
+    fn delete_one(&self, selector: bson::Document) -> Result<DeleteResult>;
+
+    fn delete_one_with_opts(
+        &self,
+        selector: bson::Document,
+        opts: Option<RemoveOne>,
+    ) -> Result<DeleteResult>;
+
+    fn find_one_and_update(
+        &self,
+        filter: bson::Document,
+        update: bson::Document,
+    ) -> Result<Option<bson::Document>>;
+
+    fn find_one_and_update_with_opts(
+        &self,
+        filter: bson::Document,
+        update: bson::Document,
+        opts: Option<FindAndModify>,
+    ) -> Result<Option<bson::Document>>;
+
+    fn find_one_and_replace(
+        &self,
+        filter: bson::Document,
+        replacement: bson::Document,
+    ) -> Result<Option<bson::Document>>;
+
+    fn find_one_and_replace_with_opts(
+        &self,
+        filter: bson::Document,
+        replacement: bson::Document,
+        opts: Option<FindAndModify>,
+    ) -> Result<Option<bson::Document>>;
+
+    fn find_one_and_delete(&self, filter: bson::Document) -> Result<Option<bson::Document>>;
+
+    fn find_one_and_delete_with_opts(
+        &self,
+        filter: bson::Document,
+        opts: Option<FindAndModify>,
+    ) -> Result<Option<bson::Document>>;
 
     fn find(&self, filter: bson::Document) -> Self::Cursor;
-    fn find_with_opts(&self, filter: bson::Document, opts: Option<FindAndModify>) -> Self::Cursor;
+    fn find_with_opts(&self, filter: bson::Document, opts: Option<FindOptions>) -> Self::Cursor;
 
-    fn aggregate(&self, pipeline: bson::Document) -> Self::Cursor;
+    fn tail(&self, filter: bson::Document, opts: Tail) -> Self::TailCursor;
+
+    fn aggregate(&self, pipeline: impl Into<Pipeline>) -> Self::Cursor;
 
     fn aggregate_with_opts(
         &self,
-        pipeline: bson::Document,
+        pipeline: impl Into<Pipeline>,
         opts: Option<Aggregate>,
     ) -> Self::Cursor;
 
     fn drop_collection(&self) -> Result<bool>;
 
+    fn with_session<'s>(&self, session: &'s Sessionc) -> SessionCollection<'s>;
+
+    fn read_prefs(&self) -> ReadPrefsc;
+    fn set_read_prefs(&self, read_prefs: &ReadPrefsc);
+    fn read_concern(&self) -> ReadConcernc;
+    fn set_read_concern(&self, read_concern: &ReadConcernc);
+    fn write_concern(&self) -> WriteConcernc;
+    fn set_write_concern(&self, write_concern: &WriteConcernc);
+
+    fn explain_find(
+        &self,
+        filter: bson::Document,
+        opts: Option<FindOptions>,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult>;
+    fn explain_aggregate(
+        &self,
+        pipeline: impl Into<Pipeline>,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult>;
+    fn explain_count(
+        &self,
+        filter: bson::Document,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult>;
+    fn explain_distinct(
+        &self,
+        field: &str,
+        filter: bson::Document,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult>;
+    fn explain_update(
+        &self,
+        selector: bson::Document,
+        update: bson::Document,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult>;
+    fn explain_delete(
+        &self,
+        selector: bson::Document,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult>;
+
+    fn bulk_write(&self) -> Result<Self::BulkWrite>;
+    fn bulk_write_with_opts(&self, opts: Option<BulkOperation>) -> Result<Self::BulkWrite>;
+
+    fn create_index(&self, index: IndexModel) -> Result<String>;
+    fn create_indexes(&self, indexes: Vec<IndexModel>) -> Result<Vec<String>>;
+    fn list_indexes(&self) -> Result<Vec<IndexModel>>;
+    fn drop_index(&self, name: &str) -> Result<()>;
+    fn drop_indexes(&self) -> Result<()>;
+
     fn watch(
         &self,
         pipeline: Option<bson::Document>,
-        opts: Option<bson::Document>,
+        opts: Option<ChangeStreamOptions>,
     ) -> Result<Self::ChangeStream>;
 }
 
 impl Collectionc {
     pub(crate) fn from_ptr(inner: *mut bindings::mongoc_collection_t) -> Self {
-        Collectionc { inner }
+        Collectionc {
+            inner,
+            session: ptr::null_mut(),
+        }
+    }
+}
+
+impl Collectionc {
+    /// Adds the session's lsid and transaction state to the opts when the collection
+    /// was obtained through `with_session`.
+    fn session_opts(&self, opts: Bsonc) -> Result<Bsonc> {
+        if self.session.is_null() {
+            return Ok(opts);
+        }
+
+        let mut error = BsoncError::empty();
+        let success = unsafe {
+            bindings::mongoc_client_session_append(
+                self.session,
+                opts.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if success {
+            Ok(opts)
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Runs the explain command for a command on the collection, in the collection's
+    /// session if it has one.  `opts` may hold a read concern, the collection's read prefs
+    /// and read concern are used when none are given.
+    fn explain(
+        &self,
+        command: bson::Document,
+        verbosity: ExplainVerbosity,
+        opts: Bsonc,
+        read_prefs: Option<ReadPrefsc>,
+    ) -> Result<ExplainResult> {
+        let bsonc_opts = self.session_opts(opts)?;
+        let command = doc! {
+            "explain": command,
+            "verbosity": verbosity.as_str(),
+        };
+
+        let mut error = BsoncError::empty();
+        let reply = Bsonc::empty();
+
+        let success = unsafe {
+            bindings::mongoc_collection_read_command_with_opts(
+                self.inner,
+                Bsonc::from_document(&command)?.as_ptr(),
+                read_prefs.as_ref().map_or(ptr::null(), ReadPrefs::as_ptr),
+                bsonc_opts.as_ptr(),
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if success {
+            Ok(ExplainResult::from_reply(reply.as_document()?))
+        } else {
+            Err(error.into())
+        }
+    }
+
+    fn name(&self) -> String {
+        let name = unsafe { CStr::from_ptr(bindings::mongoc_collection_get_name(self.inner)) };
+        name.to_string_lossy().into_owned()
+    }
+
+    /// Runs a findAndModify command returning the matched document if there was one.
+    fn find_and_modify(
+        &self,
+        filter: &bson::Document,
+        opts: &FindAndModifyOptsc,
+    ) -> Result<Option<bson::Document>> {
+        if !opts.append(&self.session_opts(Bsonc::empty())?) {
+            return Err(InvalidParamsError.into());
+        }
+
+        let mut error = BsoncError::empty();
+        let reply = Bsonc::empty();
+
+        let success = unsafe {
+            bindings::mongoc_collection_find_and_modify_with_opts(
+                self.inner,
+                Bsonc::from_document(filter)?.as_ptr(),
+                opts.as_ptr(),
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if success {
+            match reply.as_document()?.remove("value") {
+                Some(Bson::Document(doc)) => Ok(Some(doc)),
+                _ => Ok(None),
+            }
+        } else {
+            Err(error.into())
+        }
+    }
+}
+
+/// Replacement documents can't contain update operators.
+fn check_replacement(replacement: &bson::Document) -> Result<()> {
+    match replacement.keys().find(|key| key.starts_with('$')) {
+        Some(key) => Err(MongoError::InvalidReplacement(key.to_string()).into()),
+        None => Ok(()),
+    }
+}
+
+/// Generates an ObjectId `_id` for documents that don't have one so the id can be
+/// handed back to the caller without querying for it.
+pub(crate) fn ensure_id(doc: &mut bson::Document) -> Result<Bson> {
+    if let Some(id) = doc.get("_id") {
+        return Ok(id.clone());
     }
+
+    let id = Bson::ObjectId(ObjectId::new().map_err(MongoError::from)?);
+    doc.insert("_id", id.clone());
+    Ok(id)
 }
 
 impl Collection for Collectionc {
     type Cursor = Cursorc;
     type ChangeStream = ChangeStreamc;
+    type BulkWrite = BulkWritec;
+    type TailCursor = TailCursorc;
 
     /// Counts the number of documents in a collection.
     ///
@@ -186,6 +458,7 @@ impl Collection for Collectionc {
     /// let collection = db.get_collection("test");
     /// let doc = doc!{"name": "omg"};
     /// let result = collection.insert_one(doc)?;
+    /// assert!(result.inserted_id.as_object_id().is_some());
     /// let count = collection.count(None)?;
     /// assert_eq!(1, count);
     ///
@@ -193,7 +466,7 @@ impl Collection for Collectionc {
     /// # Ok(())
     /// # }
     /// ```
-    fn insert_one(&self, doc: bson::Document) -> Result<bson::Document> {
+    fn insert_one(&self, doc: bson::Document) -> Result<InsertOneResult> {
         self.insert_one_with_opts(doc, None)
     }
 
@@ -223,6 +496,7 @@ impl Collection for Collectionc {
     ///     doc!{"name": "fourth"},
     /// ];
     /// let result = collection.insert_many(docs)?;
+    /// assert_eq!(4, result.inserted_ids.len());
     /// let count = collection.count(None)?;
     /// assert_eq!(4, count);
     ///
@@ -230,7 +504,7 @@ impl Collection for Collectionc {
     /// # Ok(())
     /// # }
     /// ```
-    fn insert_many(&self, docs: Vec<bson::Document>) -> Result<bson::Document> {
+    fn insert_many(&self, docs: Vec<bson::Document>) -> Result<InsertManyResult> {
         self.insert_many_with_opts(docs, None)
     }
 
@@ -257,7 +531,8 @@ impl Collection for Collectionc {
     /// let count = collection.count(None)?;
     /// assert_eq!(1, count);
     /// let selector = doc!{"name": "omg"};
-    /// collection.delete(selector)?;
+    /// let result = collection.delete(selector)?;
+    /// assert_eq!(1, result.deleted);
     /// let count = collection.count(None)?;
     /// assert_eq!(0, count);
     ///
@@ -265,7 +540,7 @@ impl Collection for Collectionc {
     /// # Ok(())
     /// # }
     /// ```
-    fn delete(&self, doc: bson::Document) -> Result<bson::Document> {
+    fn delete(&self, doc: bson::Document) -> Result<DeleteResult> {
         self.delete_with_opts(doc, None)
     }
 
@@ -292,7 +567,9 @@ impl Collection for Collectionc {
     /// let count = collection.count(None)?;
     /// assert_eq!(1, count);
     /// let selector = doc!{"name": "omg"};
-    /// collection.update(selector, doc!{"$set": {"name": "foo"}})?;
+    /// let result = collection.update(selector, doc!{"$set": {"name": "foo"}})?;
+    /// assert_eq!(1, result.matched);
+    /// assert_eq!(1, result.modified);
     /// let count = collection.count(None)?;
     /// assert_eq!(1, count);
     ///
@@ -300,13 +577,13 @@ impl Collection for Collectionc {
     /// # Ok(())
     /// # }
     /// ```
-    fn update(&self, selector: bson::Document, update: bson::Document) -> Result<bson::Document> {
+    fn update(&self, selector: bson::Document, update: bson::Document) -> Result<UpdateResult> {
         self.update_with_opts(selector, update, None)
     }
 
-    /// Finds docs the number of documents in a collection.
+    /// Atomically updates the first document matching the filter and returns it as it was
+    /// before the update.
     ///
-    /// TODO: Add docs
     /// # Examples
     /// ```
     /// #[macro_use]
@@ -322,30 +599,27 @@ impl Collection for Collectionc {
     ///
     /// let db = client.default_database();
     /// let collection = db.get_collection("test");
-    /// let doc = doc!{"name": "omg"};
-    /// collection.insert_one(doc)?;
-    /// let doc = doc!{"name": "foo"};
-    /// collection.insert_one(doc)?;
-    /// let count = collection.count(None)?;
-    /// assert_eq!(2, count);
-    ///
-    /// let maybe: Result<Vec<bson::Document>> = collection.find(doc!{"name": "foo"}).collect();
+    /// collection.insert_one(doc!{"_id": "counter", "seq": 1})?;
+    /// let before = collection.find_one_and_update(
+    ///     doc!{"_id": "counter"},
+    ///     doc!{"$inc": {"seq": 1}},
+    /// )?;
+    /// assert_eq!(1, before.unwrap().get_i32("seq")?);
     ///
-    /// assert!(maybe.is_ok());
-    /// let records = maybe.unwrap();
-    ///
-    /// assert_eq!(1, records.len());
     /// # db.destroy();
     /// # Ok(())
     /// # }
     /// ```
-    fn find(&self, filter: bson::Document) -> Self::Cursor {
-        self.find_with_opts(filter, None)
+    fn find_one_and_update(
+        &self,
+        filter: bson::Document,
+        update: bson::Document,
+    ) -> Result<Option<bson::Document>> {
+        self.find_one_and_update_with_opts(filter, update, None)
     }
 
-    /// Finds docs the number of documents in a collection.
+    /// Atomically updates the first document matching the filter.
     ///
-    /// TODO: Add docs
     /// # Examples
     /// ```
     /// #[macro_use]
@@ -361,44 +635,36 @@ impl Collection for Collectionc {
     ///
     /// let db = client.default_database();
     /// let collection = db.get_collection("test");
-    /// let doc = doc!{"name": "omg"};
-    /// collection.insert_one(doc)?;
-    /// let doc = doc!{"name": "foo"};
-    /// collection.insert_one(doc)?;
-    /// let count = collection.count(None)?;
-    /// assert_eq!(2, count);
+    /// let opts = FindAndModify {
+    ///     upsert: true,
+    ///     return_document: ReturnDocument::After,
+    ///     ..Default::default()
+    /// };
+    /// let after = collection.find_one_and_update_with_opts(
+    ///     doc!{"_id": "counter"},
+    ///     doc!{"$inc": {"seq": 1}},
+    ///     Some(opts),
+    /// )?;
+    /// assert_eq!(1, after.unwrap().get_i32("seq")?);
     ///
-    /// let maybe: Result<Vec<bson::Document>> = collection.find(doc!{"name": "foo"}).collect();
-    ///
-    /// assert!(maybe.is_ok());
-    /// let records = maybe.unwrap();
-    ///
-    /// assert_eq!(1, records.len());
     /// # db.destroy();
     /// # Ok(())
     /// # }
     /// ```
-    fn find_with_opts(&self, filter: bson::Document, opts: Option<FindAndModify>) -> Self::Cursor {
-        let bson_filter = Bsonc::from_document(&filter).expect("should be valid");
-
-        let op = opts.unwrap_or_default();
-        let bsonc_opts = op.fields_bsonc().unwrap_or_default();
-
-        let ptr = unsafe {
-            bindings::mongoc_collection_find_with_opts(
-                self.inner,
-                bson_filter.as_ptr(),
-                bsonc_opts.as_ptr(),
-                ReadPrefsc::default().as_ptr(),
-            )
-        };
+    fn find_one_and_update_with_opts(
+        &self,
+        filter: bson::Document,
+        update: bson::Document,
+        opts: Option<FindAndModify>,
+    ) -> Result<Option<bson::Document>> {
+        let fam_opts = opts.unwrap_or_default().into_mongoc(Some(&update), false)?;
 
-        Cursorc::from_ptr(ptr)
+        self.find_and_modify(&filter, &fam_opts)
     }
 
-    /// Finds docs the number of documents in a collection.
+    /// Atomically replaces the first document matching the filter and returns it as it was
+    /// before the replacement.
     ///
-    /// TODO: Add docs
     /// # Examples
     /// ```
     /// #[macro_use]
@@ -414,40 +680,25 @@ impl Collection for Collectionc {
     ///
     /// let db = client.default_database();
     /// let collection = db.get_collection("test");
-    /// collection.insert_many(vec![
-    ///     doc!{"num": 1},
-    ///     doc!{"num": 2},
-    ///     doc!{"num": 3},
-    ///     doc!{"num": 4},
-    /// ])?;
-    ///
-    /// let maybe: Result<Vec<bson::Document>> = collection.aggregate(doc!{"pipeline": [
-    /// {
-    ///     "$group": {
-    ///         "_id": null,
-    ///         "total": { "$sum": "$num" }
-    ///     }
-    /// }
-    /// ]}).collect();
-    ///
-    /// assert!(maybe.is_ok());
-    /// let records = maybe.unwrap();
-    /// let val = dbg!(records)[0].get_i32("total");
-    ///
-    /// assert!(val.is_ok());
-    /// assert_eq!(10, val.unwrap());
+    /// collection.insert_one(doc!{"name": "omg", "extra": true})?;
+    /// let before = collection.find_one_and_replace(doc!{"name": "omg"}, doc!{"name": "foo"})?;
+    /// assert!(before.unwrap().get_bool("extra")?);
     ///
     /// # db.destroy();
     /// # Ok(())
     /// # }
     /// ```
-    fn aggregate(&self, pipeline: bson::Document) -> Self::Cursor {
-        self.aggregate_with_opts(pipeline, None)
+    fn find_one_and_replace(
+        &self,
+        filter: bson::Document,
+        replacement: bson::Document,
+    ) -> Result<Option<bson::Document>> {
+        self.find_one_and_replace_with_opts(filter, replacement, None)
     }
 
-    /// Drops collection
+    /// Atomically replaces the first document matching the filter.  The replacement document
+    /// can not contain any update operators.
     ///
-    /// TODO: Add docs
     /// # Examples
     /// ```
     /// #[macro_use]
@@ -463,46 +714,39 @@ impl Collection for Collectionc {
     ///
     /// let db = client.default_database();
     /// let collection = db.get_collection("test");
-    /// collection.drop_collection();
+    /// collection.insert_one(doc!{"name": "omg", "extra": true})?;
+    /// let opts = FindAndModify {
+    ///     return_document: ReturnDocument::After,
+    ///     fields: Some(doc!{"_id": 0}),
+    ///     ..Default::default()
+    /// };
+    /// let after = collection.find_one_and_replace_with_opts(
+    ///     doc!{"name": "omg"},
+    ///     doc!{"name": "foo"},
+    ///     Some(opts),
+    /// )?;
+    /// assert_eq!(Some(doc!{"name": "foo"}), after);
     ///
     /// # db.destroy();
     /// # Ok(())
     /// # }
     /// ```
-    fn drop_collection(&self) -> Result<bool> {
-        let mut error = BsoncError::empty();
-        let success = unsafe { bindings::mongoc_collection_drop(self.inner, error.as_mut_ptr()) };
+    fn find_one_and_replace_with_opts(
+        &self,
+        filter: bson::Document,
+        replacement: bson::Document,
+        opts: Option<FindAndModify>,
+    ) -> Result<Option<bson::Document>> {
+        check_replacement(&replacement)?;
 
-        if error.is_empty() {
-            Ok(success)
-        } else {
-            Err(error.into())
-        }
+        let fam_opts = opts
+            .unwrap_or_default()
+            .into_mongoc(Some(&replacement), false)?;
+
+        self.find_and_modify(&filter, &fam_opts)
     }
 
-    /// Counts the number of documents in a collection.
-    ///
-    /// From MongoDB Docs
-    /// filter: A bson::Document containing the filter.
-    /// opts: A bson::Document, None to ignore.
-    /// read_prefs: A mongoc_read_prefs_t or None.
-    /// opts may be None or a BSON document with additional command options:
-    ///  readConcern: Construct a mongoc_read_concern_t and use mongoc_read_concern_append() to add
-    ///  the read concern to opts. See the example code for mongoc_client_read_command_with_opts().
-    ///  Read concern requires MongoDB 3.2 or later, otherwise an error is returned.
-    ///
-    ///  sessionId: First, construct a mongoc_client_session_t with mongoc_client_start_session().
-    ///  You can begin a transaction with mongoc_client_session_start_transaction(), optionally
-    ///  with a mongoc_transaction_opt_t that overrides the options inherited from collection, and
-    ///  use mongoc_client_session_append() to add the session to opts. See the example code for
-    ///  mongoc_client_session_t.  collation: Configure textual comparisons. See Setting Collation
-    ///  Order, and the MongoDB Manual entry on Collation. Collation requires MongoDB 3.2 or later,
-    ///  otherwise an error is returned.
-    ///
-    ///  serverId: To target a specific server, include an int32 “serverId” field. Obtain the id by
-    ///  calling mongoc_client_select_server(), then mongoc_server_description_id() on its return
-    ///  value.  skip: An int specifying how many documents matching the query should be skipped
-    ///  before counting.  limit: An int specifying the maximum number of documents to count.
+    /// Atomically deletes the first document matching the filter and returns it.
     ///
     /// # Examples
     /// ```
@@ -511,52 +755,30 @@ impl Collection for Collectionc {
     /// use mongo_leaf::prelude::*;
     /// use std::env;
     ///
-    ///
     /// # fn main() -> Result<()> {
     /// env::set_var("MONGODB_URI","mongodb://standard");
-    ///
     /// let builder = Builder::new();
     /// let pool = builder.random_database_connect()?;
     /// let mut client = pool.pop();
     ///
     /// let db = client.default_database();
     /// let collection = db.get_collection("test");
-    /// let count = collection.count_with_opts(None, Some(Count{ skip: 0, limit: 10 }))?;
-    /// assert_eq!(0, count);
+    /// collection.insert_one(doc!{"name": "omg"})?;
+    /// let deleted = collection.find_one_and_delete(doc!{"name": "omg"})?;
+    /// assert!(deleted.is_some());
+    /// let deleted = collection.find_one_and_delete(doc!{"name": "omg"})?;
+    /// assert!(deleted.is_none());
     ///
     /// # db.destroy();
     /// # Ok(())
     /// # }
     /// ```
-    fn count_with_opts(&self, filter: Option<bson::Document>, opts: Option<Count>) -> Result<i64> {
-        let bsonc_filter =
-            filter.map_or_else(|| Ok(Bsonc::empty()), |d| Bsonc::from_document(&d))?;
-
-        let bsonc_opts = opts.map_or_else(|| Ok(Bsonc::empty()), |d| d.into_mongoc())?;
-
-        let reply = Bsonc::empty();
-        let mut error = BsoncError::empty();
-        let count = unsafe {
-            bindings::mongoc_collection_count_documents(
-                self.inner,
-                bsonc_filter.as_ptr(),
-                bsonc_opts.as_ptr(),
-                ptr::null_mut(), // ReadPrefs Use what is defined in the connection
-                reply.as_mut_ptr(),
-                error.as_mut_ptr(),
-            )
-        };
-
-        if count != -1 {
-            Ok(count)
-        } else {
-            Err(error.into())
-        }
+    fn find_one_and_delete(&self, filter: bson::Document) -> Result<Option<bson::Document>> {
+        self.find_one_and_delete_with_opts(filter, None)
     }
 
-    /// Inserts one doc the number of documents in a collection.
+    /// Atomically deletes the first document matching the filter and returns it.
     ///
-    /// TODO: Add docs
     /// # Examples
     /// ```
     /// #[macro_use]
@@ -571,28 +793,926 @@ impl Collection for Collectionc {
     /// let mut client = pool.pop();
     ///
     /// let db = client.default_database();
-    /// let collection = db.get_collection("test");
-    /// let doc = doc!{"name": "omg"};
-    /// let result = collection.insert_one_with_opts(doc, None)?;
-    /// let count = collection.count(None)?;
-    /// assert_eq!(1, count);
+    /// let collection = db.get_collection("jobs");
+    /// collection.insert_many(vec![
+    ///     doc!{"job": "first", "priority": 1},
+    ///     doc!{"job": "second", "priority": 2},
+    /// ])?;
+    /// let opts = FindAndModify { sort: Some(doc!{"priority": -1}), ..Default::default() };
+    /// let claimed = collection.find_one_and_delete_with_opts(doc!{}, Some(opts))?;
+    /// assert_eq!("second", claimed.unwrap().get_str("job")?);
     ///
     /// # db.destroy();
     /// # Ok(())
     /// # }
     /// ```
-    fn insert_one_with_opts(
+    fn find_one_and_delete_with_opts(
         &self,
-        doc: bson::Document,
-        opts: Option<Insert>,
-    ) -> Result<bson::Document> {
-        let mut error = BsoncError::empty();
-        let reply = Bsonc::empty();
+        filter: bson::Document,
+        opts: Option<FindAndModify>,
+    ) -> Result<Option<bson::Document>> {
+        let fam_opts = opts.unwrap_or_default().into_mongoc(None, true)?;
 
-        let bsonc_opts = opts.map_or_else(|| Ok(Bsonc::empty()), Insert::into_mongoc)?;
+        self.find_and_modify(&filter, &fam_opts)
+    }
+
+    /// Finds docs the number of documents in a collection.
+    ///
+    /// TODO: Add docs
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// let doc = doc!{"name": "omg"};
+    /// collection.insert_one(doc)?;
+    /// let doc = doc!{"name": "foo"};
+    /// collection.insert_one(doc)?;
+    /// let count = collection.count(None)?;
+    /// assert_eq!(2, count);
+    ///
+    /// let maybe: Result<Vec<bson::Document>> = collection.find(doc!{"name": "foo"}).collect();
+    ///
+    /// assert!(maybe.is_ok());
+    /// let records = maybe.unwrap();
+    ///
+    /// assert_eq!(1, records.len());
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn find(&self, filter: bson::Document) -> Self::Cursor {
+        self.find_with_opts(filter, None)
+    }
+
+    /// Finds docs in a collection using the given `FindOptions`.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// let doc = doc!{"name": "omg"};
+    /// collection.insert_one(doc)?;
+    /// let doc = doc!{"name": "foo"};
+    /// collection.insert_one(doc)?;
+    /// let count = collection.count(None)?;
+    /// assert_eq!(2, count);
+    ///
+    /// let opts = FindOptions {
+    ///     sort: Some(doc!{"name": 1}),
+    ///     projection: Some(doc!{"_id": 0}),
+    ///     limit: Some(1),
+    ///     ..Default::default()
+    /// };
+    /// let maybe: Result<Vec<bson::Document>> = collection.find_with_opts(doc!{}, Some(opts)).collect();
+    ///
+    /// assert!(maybe.is_ok());
+    /// let records = maybe.unwrap();
+    ///
+    /// assert_eq!(vec![doc!{"name": "foo"}], records);
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn find_with_opts(&self, filter: bson::Document, opts: Option<FindOptions>) -> Self::Cursor {
+        let bson_filter = Bsonc::from_document(&filter).expect("should be valid");
+
+        let (bsonc_opts, read_prefs) = match opts
+            .unwrap_or_default()
+            .into_mongoc()
+            .and_then(|(opts, read_prefs)| Ok((self.session_opts(opts)?, read_prefs)))
+        {
+            Ok(opts) => opts,
+            Err(error) => return Cursorc::from_error(error),
+        };
+
+        let ptr = unsafe {
+            bindings::mongoc_collection_find_with_opts(
+                self.inner,
+                bson_filter.as_ptr(),
+                bsonc_opts.as_ptr(),
+                read_prefs.as_ref().map_or(ptr::null(), ReadPrefs::as_ptr),
+            )
+        };
+
+        Cursorc::from_ptr(ptr)
+    }
+
+    /// Follows a capped collection, waiting for documents inserted after the ones
+    /// already there.  The cursor is opened again after an error, resuming after the
+    /// last seen `_id`, until `max_retries` errors happen in a row.  `PooledCollection::tail`
+    /// waits on a worker thread instead of the calling one.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// client.command_simple(db.name(), doc!{"create": "log", "capped": true, "size": 4096}, None)?;
+    ///
+    /// let collection = db.get_collection("log");
+    /// collection.insert_one(doc!{"level": "info", "msg": "started"})?;
+    ///
+    /// let mut tail = collection.tail(doc!{"level": "info"}, Tail::default());
+    /// let first = tail.next().unwrap()?;
+    /// assert_eq!("started", first.get_str("msg")?);
+    /// assert_eq!(first.get("_id"), tail.last_id());
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn tail(&self, filter: bson::Document, opts: Tail) -> Self::TailCursor {
+        let inner = unsafe { bindings::mongoc_collection_copy(self.inner) };
+        assert!(!inner.is_null());
+
+        // Tailable cursors can't be used in transactions, the cursor may also outlive
+        // the session.
+        TailCursorc::new(Collectionc::from_ptr(inner), filter, opts)
+    }
+
+    /// Finds docs the number of documents in a collection.
+    ///
+    /// TODO: Add docs
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// collection.insert_many(vec![
+    ///     doc!{"num": 1},
+    ///     doc!{"num": 2},
+    ///     doc!{"num": 3},
+    ///     doc!{"num": 4},
+    /// ])?;
+    ///
+    /// let maybe: Result<Vec<bson::Document>> = collection.aggregate(doc!{"pipeline": [
+    /// {
+    ///     "$group": {
+    ///         "_id": null,
+    ///         "total": { "$sum": "$num" }
+    ///     }
+    /// }
+    /// ]}).collect();
+    ///
+    /// assert!(maybe.is_ok());
+    /// let records = maybe.unwrap();
+    /// let val = dbg!(records)[0].get_i32("total");
+    ///
+    /// assert!(val.is_ok());
+    /// assert_eq!(10, val.unwrap());
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn aggregate(&self, pipeline: impl Into<Pipeline>) -> Self::Cursor {
+        self.aggregate_with_opts(pipeline, None)
+    }
+
+    /// Drops collection
+    ///
+    /// TODO: Add docs
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// collection.drop_collection();
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn drop_collection(&self) -> Result<bool> {
+        let mut error = BsoncError::empty();
+        let bsonc_opts = self.session_opts(Bsonc::empty())?;
+        let success = unsafe {
+            bindings::mongoc_collection_drop_with_opts(
+                self.inner,
+                bsonc_opts.as_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if error.is_empty() {
+            Ok(success)
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Returns a copy of the collection that runs every operation in the session, so
+    /// the operations take part in the session's transaction.  The copy, and the cursors,
+    /// change streams and bulk writes it returns, borrow the session.  Tailing it doesn't
+    /// use the session.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard/");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    /// let db = client.default_database();
+    /// let session = client.start_session(None)?;
+    ///
+    /// let accounts = db.get_collection("accounts");
+    /// accounts.insert_many(vec![
+    ///     doc!{"_id": "alice", "balance": 100},
+    ///     doc!{"_id": "bob", "balance": 0},
+    /// ])?;
+    ///
+    /// session.start_transaction(None)?;
+    /// let in_txn = accounts.with_session(&session);
+    /// in_txn.update_one(doc!{"_id": "alice"}, doc!{"$inc": {"balance": -50}})?;
+    /// in_txn.update_one(doc!{"_id": "bob"}, doc!{"$inc": {"balance": 50}})?;
+    /// session.commit()?;
+    ///
+    /// let count = accounts.count_documents(doc!{"balance": 50}, None)?;
+    /// assert_eq!(2, count);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn with_session<'s>(&self, session: &'s Sessionc) -> SessionCollection<'s> {
+        let inner = unsafe { bindings::mongoc_collection_copy(self.inner) };
+        assert!(!inner.is_null());
+
+        let collection = Collectionc {
+            inner,
+            session: session.as_mut_ptr(),
+        };
+
+        SessionCollection::new(collection, session)
+    }
+
+    /// The read prefs used by operations on the collection that don't set their own.
+    fn read_prefs(&self) -> ReadPrefsc {
+        ReadPrefsc::copy_from_ptr(unsafe { bindings::mongoc_collection_get_read_prefs(self.inner) })
+    }
+
+    /// Sets the read prefs inherited by operations on the collection.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("analytics");
+    /// collection.set_read_prefs(&ReadPrefsc::new(&ReadMode::SecondaryPreferred));
+    /// collection.set_read_concern(&ReadConcernc::new(ReadConcernLevel::Majority)?);
+    /// collection.set_write_concern(&WriteConcernc::new(WriteConcernLevel::Majority, None));
+    ///
+    /// collection.insert_one(doc!{"event": "signup"})?;
+    /// assert_eq!(1, collection.count_documents(doc!{}, None)?);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn set_read_prefs(&self, read_prefs: &ReadPrefsc) {
+        unsafe { bindings::mongoc_collection_set_read_prefs(self.inner, read_prefs.as_ptr()) }
+    }
+
+    /// The read concern used by operations on the collection that don't set their own.
+    fn read_concern(&self) -> ReadConcernc {
+        ReadConcernc::copy_from_ptr(unsafe {
+            bindings::mongoc_collection_get_read_concern(self.inner)
+        })
+    }
+
+    /// Sets the read concern inherited by operations on the collection.
+    fn set_read_concern(&self, read_concern: &ReadConcernc) {
+        unsafe { bindings::mongoc_collection_set_read_concern(self.inner, read_concern.as_ptr()) }
+    }
+
+    /// The write concern used by operations on the collection that don't set their own.
+    fn write_concern(&self) -> WriteConcernc {
+        WriteConcernc::copy_from_ptr(unsafe {
+            bindings::mongoc_collection_get_write_concern(self.inner)
+        })
+    }
+
+    /// Sets the write concern inherited by operations on the collection.
+    fn set_write_concern(&self, write_concern: &WriteConcernc) {
+        unsafe { bindings::mongoc_collection_set_write_concern(self.inner, write_concern.as_ptr()) }
+    }
+
+    /// Explains how the server runs a find, with the read prefs and read concern of `opts`
+    /// and in the collection's session if it has one.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// collection.insert_many(vec![doc!{"name": "omg"}, doc!{"name": "foo"}])?;
+    ///
+    /// let plan = collection.explain_find(doc!{"name": "foo"}, None, ExplainVerbosity::ExecutionStats)?;
+    /// assert!(plan.collection_scan);
+    /// assert_eq!(Some(2), plan.docs_examined);
+    /// assert_eq!(Some(1), plan.docs_returned);
+    ///
+    /// collection.create_index(IndexModel::new(doc!{"name": 1}))?;
+    /// let plan = collection.explain_find(doc!{"name": "foo"}, None, ExplainVerbosity::QueryPlanner)?;
+    /// assert_eq!(vec!["name_1".to_string()], plan.indexes_used);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn explain_find(
+        &self,
+        filter: bson::Document,
+        opts: Option<FindOptions>,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult> {
+        let (fields, bsonc_opts, read_prefs) = opts.unwrap_or_default().into_explain()?;
+        let mut command = doc! {
+            "find": self.name(),
+            "filter": filter,
+        };
+        for (key, value) in fields {
+            command.insert(key, value);
+        }
+
+        self.explain(command, verbosity, bsonc_opts, read_prefs)
+    }
+
+    /// Explains how the server runs an aggregation.
+    fn explain_aggregate(
+        &self,
+        pipeline: impl Into<Pipeline>,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult> {
+        let command = doc! {
+            "aggregate": self.name(),
+            "pipeline": pipeline.into().into_bson(),
+            "cursor": {},
+        };
+
+        self.explain(command, verbosity, Bsonc::empty(), None)
+    }
+
+    /// Explains how the server counts the documents matching the filter.
+    fn explain_count(
+        &self,
+        filter: bson::Document,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult> {
+        let command = doc! {
+            "count": self.name(),
+            "query": filter,
+        };
+
+        self.explain(command, verbosity, Bsonc::empty(), None)
+    }
+
+    /// Explains how the server finds the distinct values of a field.
+    fn explain_distinct(
+        &self,
+        field: &str,
+        filter: bson::Document,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult> {
+        let command = doc! {
+            "distinct": self.name(),
+            "key": field,
+            "query": filter,
+        };
+
+        self.explain(command, verbosity, Bsonc::empty(), None)
+    }
+
+    /// Explains how the server updates the documents matching the selector.  With
+    /// execution stats the update is evaluated but not applied.
+    fn explain_update(
+        &self,
+        selector: bson::Document,
+        update: bson::Document,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult> {
+        let command = doc! {
+            "update": self.name(),
+            "updates": [{ "q": selector, "u": update, "multi": true }],
+        };
+
+        self.explain(command, verbosity, Bsonc::empty(), None)
+    }
+
+    /// Explains how the server deletes the documents matching the selector.  With
+    /// execution stats the delete is evaluated but not applied.
+    fn explain_delete(
+        &self,
+        selector: bson::Document,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult> {
+        let command = doc! {
+            "delete": self.name(),
+            "deletes": [{ "q": selector, "limit": 0 }],
+        };
+
+        self.explain(command, verbosity, Bsonc::empty(), None)
+    }
+
+    /// Counts the number of documents in a collection.
+    ///
+    /// From MongoDB Docs
+    /// filter: A bson::Document containing the filter.
+    /// opts: A bson::Document, None to ignore.
+    /// read_prefs: A mongoc_read_prefs_t or None.
+    /// opts may be None or a BSON document with additional command options:
+    ///  readConcern: Construct a mongoc_read_concern_t and use mongoc_read_concern_append() to add
+    ///  the read concern to opts. See the example code for mongoc_client_read_command_with_opts().
+    ///  Read concern requires MongoDB 3.2 or later, otherwise an error is returned.
+    ///
+    ///  sessionId: First, construct a mongoc_client_session_t with mongoc_client_start_session().
+    ///  You can begin a transaction with mongoc_client_session_start_transaction(), optionally
+    ///  with a mongoc_transaction_opt_t that overrides the options inherited from collection, and
+    ///  use mongoc_client_session_append() to add the session to opts. See the example code for
+    ///  mongoc_client_session_t.  collation: Configure textual comparisons. See Setting Collation
+    ///  Order, and the MongoDB Manual entry on Collation. Collation requires MongoDB 3.2 or later,
+    ///  otherwise an error is returned.
+    ///
+    ///  serverId: To target a specific server, include an int32 “serverId” field. Obtain the id by
+    ///  calling mongoc_client_select_server(), then mongoc_server_description_id() on its return
+    ///  value.  skip: An int specifying how many documents matching the query should be skipped
+    ///  before counting.  limit: An int specifying the maximum number of documents to count.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    ///
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// let count = collection.count_with_opts(None, Some(Count{ skip: 0, limit: 10, ..Default::default() }))?;
+    /// assert_eq!(0, count);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn count_with_opts(&self, filter: Option<bson::Document>, opts: Option<Count>) -> Result<i64> {
+        let bsonc_filter =
+            filter.map_or_else(|| Ok(Bsonc::empty()), |d| Bsonc::from_document(&d))?;
+
+        let (bsonc_opts, read_prefs) =
+            opts.map_or_else(|| Ok((Bsonc::empty(), None)), Count::into_mongoc)?;
+        let bsonc_opts = self.session_opts(bsonc_opts)?;
+
+        let reply = Bsonc::empty();
+        let mut error = BsoncError::empty();
+        let count = unsafe {
+            bindings::mongoc_collection_count_documents(
+                self.inner,
+                bsonc_filter.as_ptr(),
+                bsonc_opts.as_ptr(),
+                read_prefs.as_ref().map_or(ptr::null(), ReadPrefs::as_ptr),
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if count != -1 {
+            Ok(count)
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Counts the documents matching the filter with an aggregation, the count is
+    /// accurate but has to scan the matching documents.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// collection.insert_many(vec![
+    ///     doc!{"name": "omg", "num": 1},
+    ///     doc!{"name": "omg", "num": 2},
+    ///     doc!{"name": "foo", "num": 3},
+    /// ])?;
+    ///
+    /// let opts = CountDocuments { max_time_ms: Some(1000), ..Default::default() };
+    /// let count = collection.count_documents(doc!{"name": "omg"}, Some(opts))?;
+    /// assert_eq!(2, count);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn count_documents(&self, filter: bson::Document, opts: Option<CountDocuments>) -> Result<i64> {
+        let (bsonc_opts, read_prefs) = opts.unwrap_or_default().into_mongoc()?;
+        let bsonc_opts = self.session_opts(bsonc_opts)?;
+
+        let reply = Bsonc::empty();
+        let mut error = BsoncError::empty();
+        let count = unsafe {
+            bindings::mongoc_collection_count_documents(
+                self.inner,
+                Bsonc::from_document(&filter)?.as_ptr(),
+                bsonc_opts.as_ptr(),
+                read_prefs.as_ref().map_or(ptr::null(), ReadPrefs::as_ptr),
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if count != -1 {
+            Ok(count)
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Estimates the number of documents in the collection from its metadata, this is
+    /// fast but may be off after an unclean shutdown or with orphaned documents.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// collection.insert_many(vec![doc!{"num": 1}, doc!{"num": 2}])?;
+    ///
+    /// let count = collection.estimated_document_count(None)?;
+    /// assert_eq!(2, count);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn estimated_document_count(&self, opts: Option<EstimatedDocumentCount>) -> Result<i64> {
+        let (bsonc_opts, read_prefs) = opts.unwrap_or_default().into_mongoc()?;
+        let bsonc_opts = self.session_opts(bsonc_opts)?;
+
+        let reply = Bsonc::empty();
+        let mut error = BsoncError::empty();
+        let count = unsafe {
+            bindings::mongoc_collection_estimated_document_count(
+                self.inner,
+                bsonc_opts.as_ptr(),
+                read_prefs.as_ref().map_or(ptr::null(), ReadPrefs::as_ptr),
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if count != -1 {
+            Ok(count)
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Finds the distinct values of a field across the documents matching the filter.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// collection.insert_many(vec![
+    ///     doc!{"name": "omg", "num": 1},
+    ///     doc!{"name": "omg", "num": 2},
+    ///     doc!{"name": "foo", "num": 3},
+    /// ])?;
+    ///
+    /// let names = collection.distinct("name", Some(doc!{"num": {"$lt": 3}}), None)?;
+    /// assert_eq!(vec![bson::Bson::String("omg".to_string())], names);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn distinct(
+        &self,
+        field: &str,
+        filter: Option<bson::Document>,
+        opts: Option<Distinct>,
+    ) -> Result<Vec<Bson>> {
+        let (bsonc_opts, read_prefs) = opts.unwrap_or_default().into_mongoc()?;
+        let bsonc_opts = self.session_opts(bsonc_opts)?;
+        let command = doc! {
+            "distinct": self.name(),
+            "key": field,
+            "query": filter.unwrap_or_default(),
+        };
+
+        let mut error = BsoncError::empty();
+        let reply = Bsonc::empty();
+
+        let success = unsafe {
+            bindings::mongoc_collection_read_command_with_opts(
+                self.inner,
+                Bsonc::from_document(&command)?.as_ptr(),
+                read_prefs.as_ref().map_or(ptr::null(), ReadPrefs::as_ptr),
+                bsonc_opts.as_ptr(),
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if success {
+            match reply.as_document()?.remove("values") {
+                Some(Bson::Array(values)) => Ok(values),
+                _ => Ok(Vec::new()),
+            }
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Inserts one doc the number of documents in a collection.
+    ///
+    /// TODO: Add docs
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// let doc = doc!{"_id": 42, "name": "omg"};
+    /// let result = collection.insert_one_with_opts(doc, None)?;
+    /// assert_eq!(bson::Bson::I32(42), result.inserted_id);
+    /// let count = collection.count(None)?;
+    /// assert_eq!(1, count);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn insert_one_with_opts(
+        &self,
+        mut doc: bson::Document,
+        opts: Option<Insert>,
+    ) -> Result<InsertOneResult> {
+        let mut error = BsoncError::empty();
+        let reply = Bsonc::empty();
+
+        let bsonc_opts = opts.map_or_else(|| Ok(Bsonc::empty()), Insert::into_mongoc)?;
+        let bsonc_opts = self.session_opts(bsonc_opts)?;
+        let inserted_id = ensure_id(&mut doc)?;
+
+        let success = unsafe {
+            bindings::mongoc_collection_insert_one(
+                self.inner,
+                Bsonc::from_document(&doc)?.as_ptr(),
+                bsonc_opts.as_ptr(),
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if success {
+            Ok(InsertOneResult { inserted_id })
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Inserts many docs the number of documents in a collection.
+    ///
+    /// TODO: Add docs
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// let docs = vec![
+    ///     doc!{"name": "first"},
+    ///     doc!{"name": "second"},
+    ///     doc!{"name": "third"},
+    ///     doc!{"name": "fourth"},
+    /// ];
+    /// let result = collection.insert_many_with_opts(docs, None)?;
+    /// assert_eq!(4, result.inserted_ids.len());
+    /// let count = collection.count(None)?;
+    /// assert_eq!(4, count);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn insert_many_with_opts(
+        &self,
+        mut docs: Vec<bson::Document>,
+        opts: Option<Insert>,
+    ) -> Result<InsertManyResult> {
+        let mut error = BsoncError::empty();
+        let reply = Bsonc::empty();
+
+        let bsonc_opts = opts.map_or_else(|| Ok(Bsonc::empty()), Insert::into_mongoc)?;
+        let bsonc_opts = self.session_opts(bsonc_opts)?;
+        let inserted_ids = docs
+            .iter_mut()
+            .map(ensure_id)
+            .collect::<Result<Vec<Bson>>>()?;
+
+        let bsonc: Vec<Bsonc> = docs
+            .iter()
+            .map(|d| Bsonc::from_document(&d).expect("should be valid doc"))
+            .collect();
+
+        let ptrs: Vec<*const bindings::bson_t> = bsonc.iter().map(|b| b.as_ptr()).collect();
 
         let success = unsafe {
-            bindings::mongoc_collection_insert_one(
+            bindings::mongoc_collection_insert_many(
+                self.inner,
+                ptrs.as_ptr() as *mut *const bindings::bson_t,
+                docs.len(),
+                bsonc_opts.as_ptr(),
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if success {
+            Ok(InsertManyResult { inserted_ids })
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Deletes one doc the number of documents in a collection.
+    ///
+    /// TODO: Add docs
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// let doc = doc!{"name": "omg"};
+    /// collection.insert_one(doc)?;
+    /// let count = collection.count(None)?;
+    /// assert_eq!(1, count);
+    /// let selector = doc!{"name": "omg"};
+    /// let result = collection.delete_with_opts(selector, None)?;
+    /// assert_eq!(1, result.deleted);
+    /// let count = collection.count(None)?;
+    /// assert_eq!(0, count);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn delete_with_opts(&self, doc: bson::Document, opts: Option<Remove>) -> Result<DeleteResult> {
+        let mut error = BsoncError::empty();
+        let reply = Bsonc::empty();
+
+        let bsonc_opts = opts.map_or_else(|| Ok(Bsonc::empty()), Remove::into_mongoc)?;
+        let bsonc_opts = self.session_opts(bsonc_opts)?;
+
+        let success = unsafe {
+            bindings::mongoc_collection_delete_many(
                 self.inner,
                 Bsonc::from_document(&doc)?.as_ptr(),
                 bsonc_opts.as_ptr(),
@@ -602,15 +1722,268 @@ impl Collection for Collectionc {
         };
 
         if success {
-            reply.as_document()
+            Ok(DeleteResult::from_reply(&reply.as_document()?))
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Updates one doc the number of documents in a collection.
+    ///
+    /// TODO: Add docs
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// let doc = doc!{"name": "omg"};
+    /// collection.insert_one(doc)?;
+    /// let count = collection.count(None)?;
+    /// assert_eq!(1, count);
+    /// let selector = doc!{"name": "omg"};
+    /// let result = collection.update_with_opts(selector, doc!{"$set": {"name": "foo"}}, None)?;
+    /// assert_eq!(1, result.matched);
+    /// assert_eq!(1, result.modified);
+    /// let count = collection.count(None)?;
+    /// assert_eq!(1, count);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn update_with_opts(
+        &self,
+        selector: bson::Document,
+        update: bson::Document,
+        opts: Option<Update>,
+    ) -> Result<UpdateResult> {
+        let mut error = BsoncError::empty();
+        let reply = Bsonc::empty();
+
+        let bsonc_opts = opts.map_or_else(|| Ok(Bsonc::empty()), Update::into_mongoc)?;
+        let bsonc_opts = self.session_opts(bsonc_opts)?;
+
+        let success = unsafe {
+            bindings::mongoc_collection_update_many(
+                self.inner,
+                Bsonc::from_document(&selector)?.as_ptr(),
+                Bsonc::from_document(&update)?.as_ptr(),
+                bsonc_opts.as_ptr(),
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if success {
+            Ok(UpdateResult::from_reply(&reply.as_document()?))
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Updates the first document matching the selector.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// collection.insert_many(vec![doc!{"name": "omg"}, doc!{"name": "omg"}])?;
+    /// let result = collection.update_one(doc!{"name": "omg"}, doc!{"$set": {"name": "foo"}})?;
+    /// assert_eq!(1, result.modified);
+    /// let count = collection.count(Some(doc!{"name": "omg"}))?;
+    /// assert_eq!(1, count);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn update_one(&self, selector: bson::Document, update: bson::Document) -> Result<UpdateResult> {
+        self.update_one_with_opts(selector, update, None)
+    }
+
+    /// Updates the first document matching the selector.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// let opts = UpdateOne { upsert: true, ..Default::default() };
+    /// let result = collection.update_one_with_opts(
+    ///     doc!{"name": "omg"},
+    ///     doc!{"$set": {"name": "foo"}},
+    ///     Some(opts),
+    /// )?;
+    /// assert_eq!(0, result.matched);
+    /// assert!(result.upserted_id.is_some());
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn update_one_with_opts(
+        &self,
+        selector: bson::Document,
+        update: bson::Document,
+        opts: Option<UpdateOne>,
+    ) -> Result<UpdateResult> {
+        let mut error = BsoncError::empty();
+        let reply = Bsonc::empty();
+
+        let bsonc_opts = opts.map_or_else(|| Ok(Bsonc::empty()), UpdateOne::into_mongoc)?;
+        let bsonc_opts = self.session_opts(bsonc_opts)?;
+
+        let success = unsafe {
+            bindings::mongoc_collection_update_one(
+                self.inner,
+                Bsonc::from_document(&selector)?.as_ptr(),
+                Bsonc::from_document(&update)?.as_ptr(),
+                bsonc_opts.as_ptr(),
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if success {
+            Ok(UpdateResult::from_reply(&reply.as_document()?))
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Replaces the first document matching the selector.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// collection.insert_one(doc!{"name": "omg", "extra": true})?;
+    /// let result = collection.replace_one(doc!{"name": "omg"}, doc!{"name": "foo"})?;
+    /// assert_eq!(1, result.modified);
+    /// let count = collection.count(Some(doc!{"extra": true}))?;
+    /// assert_eq!(0, count);
+    ///
+    /// let invalid = collection.replace_one(doc!{"name": "foo"}, doc!{"$set": {"name": "bar"}});
+    /// assert!(invalid.is_err());
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn replace_one(
+        &self,
+        selector: bson::Document,
+        replacement: bson::Document,
+    ) -> Result<UpdateResult> {
+        self.replace_one_with_opts(selector, replacement, None)
+    }
+
+    /// Replaces the first document matching the selector.  The replacement document can not
+    /// contain any update operators.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// let opts = ReplaceOne { upsert: true, ..Default::default() };
+    /// let result = collection.replace_one_with_opts(
+    ///     doc!{"name": "omg"},
+    ///     doc!{"name": "foo"},
+    ///     Some(opts),
+    /// )?;
+    /// assert!(result.upserted_id.is_some());
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn replace_one_with_opts(
+        &self,
+        selector: bson::Document,
+        replacement: bson::Document,
+        opts: Option<ReplaceOne>,
+    ) -> Result<UpdateResult> {
+        check_replacement(&replacement)?;
+
+        let mut error = BsoncError::empty();
+        let reply = Bsonc::empty();
+
+        let bsonc_opts = opts.map_or_else(|| Ok(Bsonc::empty()), ReplaceOne::into_mongoc)?;
+        let bsonc_opts = self.session_opts(bsonc_opts)?;
+
+        let success = unsafe {
+            bindings::mongoc_collection_replace_one(
+                self.inner,
+                Bsonc::from_document(&selector)?.as_ptr(),
+                Bsonc::from_document(&replacement)?.as_ptr(),
+                bsonc_opts.as_ptr(),
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if success {
+            Ok(UpdateResult::from_reply(&reply.as_document()?))
         } else {
             Err(error.into())
         }
     }
 
-    /// Inserts many docs the number of documents in a collection.
+    /// Deletes the first document matching the selector.
     ///
-    /// TODO: Add docs
     /// # Examples
     /// ```
     /// #[macro_use]
@@ -621,48 +1994,65 @@ impl Collection for Collectionc {
     /// # fn main() -> Result<()> {
     /// env::set_var("MONGODB_URI","mongodb://standard");
     /// let builder = Builder::new();
-    /// let builder = Builder::new();
     /// let pool = builder.random_database_connect()?;
     /// let mut client = pool.pop();
     ///
     /// let db = client.default_database();
     /// let collection = db.get_collection("test");
-    /// let docs = vec![
-    ///     doc!{"name": "first"},
-    ///     doc!{"name": "second"},
-    ///     doc!{"name": "third"},
-    ///     doc!{"name": "fourth"},
-    /// ];
-    /// let result = collection.insert_many_with_opts(docs, None)?;
+    /// collection.insert_many(vec![doc!{"name": "omg"}, doc!{"name": "omg"}])?;
+    /// let result = collection.delete_one(doc!{"name": "omg"})?;
+    /// assert_eq!(1, result.deleted);
     /// let count = collection.count(None)?;
-    /// assert_eq!(4, count);
+    /// assert_eq!(1, count);
     ///
     /// # db.destroy();
     /// # Ok(())
     /// # }
     /// ```
-    fn insert_many_with_opts(
+    fn delete_one(&self, selector: bson::Document) -> Result<DeleteResult> {
+        self.delete_one_with_opts(selector, None)
+    }
+
+    /// Deletes the first document matching the selector.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// collection.insert_many(vec![doc!{"name": "omg"}, doc!{"name": "omg"}])?;
+    /// let result = collection.delete_one_with_opts(doc!{"name": "omg"}, None)?;
+    /// assert_eq!(1, result.deleted);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn delete_one_with_opts(
         &self,
-        docs: Vec<bson::Document>,
-        opts: Option<Insert>,
-    ) -> Result<bson::Document> {
+        selector: bson::Document,
+        opts: Option<RemoveOne>,
+    ) -> Result<DeleteResult> {
         let mut error = BsoncError::empty();
         let reply = Bsonc::empty();
 
-        let bsonc_opts = opts.map_or_else(|| Ok(Bsonc::empty()), Insert::into_mongoc)?;
-
-        let bsonc: Vec<Bsonc> = docs
-            .iter()
-            .map(|d| Bsonc::from_document(&d).expect("should be valid doc"))
-            .collect();
-
-        let ptrs: Vec<*const bindings::bson_t> = bsonc.iter().map(|b| b.as_ptr()).collect();
+        let bsonc_opts = opts.map_or_else(|| Ok(Bsonc::empty()), RemoveOne::into_mongoc)?;
+        let bsonc_opts = self.session_opts(bsonc_opts)?;
 
         let success = unsafe {
-            bindings::mongoc_collection_insert_many(
+            bindings::mongoc_collection_delete_one(
                 self.inner,
-                ptrs.as_ptr() as *mut *const bindings::bson_t,
-                docs.len(),
+                Bsonc::from_document(&selector)?.as_ptr(),
                 bsonc_opts.as_ptr(),
                 reply.as_mut_ptr(),
                 error.as_mut_ptr(),
@@ -670,13 +2060,13 @@ impl Collection for Collectionc {
         };
 
         if success {
-            reply.as_document()
+            Ok(DeleteResult::from_reply(&reply.as_document()?))
         } else {
             Err(error.into())
         }
     }
 
-    /// Deletes one doc the number of documents in a collection.
+    /// Finds docs the number of documents in a collection.
     ///
     /// TODO: Add docs
     /// # Examples
@@ -694,49 +2084,70 @@ impl Collection for Collectionc {
     ///
     /// let db = client.default_database();
     /// let collection = db.get_collection("test");
-    /// let doc = doc!{"name": "omg"};
-    /// collection.insert_one(doc)?;
-    /// let count = collection.count(None)?;
-    /// assert_eq!(1, count);
-    /// let selector = doc!{"name": "omg"};
-    /// collection.delete_with_opts(selector, None)?;
-    /// let count = collection.count(None)?;
-    /// assert_eq!(0, count);
+    /// collection.insert_many(vec![
+    ///     doc!{"num": 1},
+    ///     doc!{"num": 2},
+    ///     doc!{"num": 3},
+    ///     doc!{"num": 4},
+    /// ])?;
+    ///
+    /// let pipeline = Pipeline::new()
+    ///     .filter(doc!{"num": {"$gt": 0}})
+    ///     .group(doc!{"_id": null, "total": {"$sum": "$num"}});
+    /// let opts = Aggregate { allow_disk_use: true, ..Default::default() };
+    ///
+    /// let maybe: Result<Vec<bson::Document>> =
+    ///     collection.aggregate_with_opts(pipeline, Some(opts)).collect();
+    ///
+    /// assert!(maybe.is_ok());
+    /// let records = maybe.unwrap();
+    /// let val = dbg!(records)[0].get_i32("total");
     ///
+    /// assert!(val.is_ok());
+    /// assert_eq!(10, val.unwrap());
     /// # db.destroy();
     /// # Ok(())
     /// # }
     /// ```
-    fn delete_with_opts(
+    fn aggregate_with_opts(
         &self,
-        doc: bson::Document,
-        opts: Option<Remove>,
-    ) -> Result<bson::Document> {
-        let mut error = BsoncError::empty();
-        let reply = Bsonc::empty();
+        pipeline: impl Into<Pipeline>,
+        opts: Option<Aggregate>,
+    ) -> Cursorc {
+        let bsonc_pipeline =
+            Bsonc::from_document(&pipeline.into().into_document()).expect("should be valid");
 
-        let bsonc_opts = opts.map_or_else(|| Ok(Bsonc::empty()), Remove::into_mongoc)?;
+        let agg_opts = opts.unwrap_or_default();
+        let flags = agg_opts.query_flags.flags();
+        let (bsonc_opts, read_prefs) = match agg_opts
+            .into_mongoc()
+            .and_then(|(opts, read_prefs)| Ok((self.session_opts(opts)?, read_prefs)))
+        {
+            Ok(opts) => opts,
+            Err(error) => return Cursorc::from_error(error),
+        };
 
-        let success = unsafe {
-            bindings::mongoc_collection_delete_many(
+        let ptr = unsafe {
+            bindings::mongoc_collection_aggregate(
                 self.inner,
-                Bsonc::from_document(&doc)?.as_ptr(),
+                flags,
+                bsonc_pipeline.as_ptr(),
                 bsonc_opts.as_ptr(),
-                reply.as_mut_ptr(),
-                error.as_mut_ptr(),
+                read_prefs.as_ref().map_or(ptr::null(), ReadPrefs::as_ptr),
             )
         };
 
-        if success {
-            reply.as_document()
-        } else {
-            Err(error.into())
-        }
+        Cursorc::from_ptr(ptr)
     }
 
-    /// Updates one doc the number of documents in a collection.
+    /// Starts a bulk write on the collection, see `BulkWrite`.
+    fn bulk_write(&self) -> Result<Self::BulkWrite> {
+        self.bulk_write_with_opts(None)
+    }
+
+    /// Starts a bulk write on the collection.  Writes are ordered unless
+    /// configured otherwise.
     ///
-    /// TODO: Add docs
     /// # Examples
     /// ```
     /// #[macro_use]
@@ -752,35 +2163,126 @@ impl Collection for Collectionc {
     ///
     /// let db = client.default_database();
     /// let collection = db.get_collection("test");
-    /// let doc = doc!{"name": "omg"};
-    /// collection.insert_one(doc)?;
-    /// let count = collection.count(None)?;
-    /// assert_eq!(1, count);
-    /// let selector = doc!{"name": "omg"};
-    /// collection.update_with_opts(selector, doc!{"$set": {"name": "foo"}}, None)?;
-    /// let count = collection.count(None)?;
-    /// assert_eq!(1, count);
+    /// collection.insert_many(vec![doc!{"name": "omg"}, doc!{"name": "stale"}])?;
+    ///
+    /// let opts = BulkOperation { ordered: false, ..Default::default() };
+    /// let mut bulk = collection.bulk_write_with_opts(Some(opts))?;
+    /// bulk.append(WriteModel::ReplaceOne {
+    ///     filter: doc!{"name": "new"},
+    ///     replacement: doc!{"name": "new"},
+    ///     upsert: true,
+    /// })?
+    /// .append(WriteModel::DeleteMany { filter: doc!{"name": "stale"} })?;
+    ///
+    /// let result = bulk.execute()?;
+    /// assert_eq!(1, result.deleted);
+    /// assert_eq!(1, result.upserted_ids.len());
     ///
     /// # db.destroy();
     /// # Ok(())
     /// # }
     /// ```
-    fn update_with_opts(
-        &self,
-        selector: bson::Document,
-        update: bson::Document,
-        opts: Option<Update>,
-    ) -> Result<bson::Document> {
+    fn bulk_write_with_opts(&self, opts: Option<BulkOperation>) -> Result<Self::BulkWrite> {
+        // mongoc orders bulks by default.
+        let ordered = match opts {
+            Some(ref opts) => opts.ordered,
+            None => true,
+        };
+        let bsonc_opts = opts.map_or_else(|| Ok(Bsonc::empty()), BulkOperation::into_mongoc)?;
+        let bsonc_opts = self.session_opts(bsonc_opts)?;
+
+        let inner = unsafe {
+            bindings::mongoc_collection_create_bulk_operation_with_opts(
+                self.inner,
+                bsonc_opts.as_ptr(),
+            )
+        };
+
+        Ok(BulkWritec::from_ptr(inner, ordered))
+    }
+
+    /// Creates an index on the collection, returning its name.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// let index = IndexModel {
+    ///     unique: true,
+    ///     ..IndexModel::new(doc!{"email": 1})
+    /// };
+    /// let name = collection.create_index(index)?;
+    /// assert_eq!("email_1", name);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn create_index(&self, index: IndexModel) -> Result<String> {
+        let mut names = self.create_indexes(vec![index])?;
+        Ok(names.remove(0))
+    }
+
+    /// Creates several indexes with a single createIndexes command, returning their names.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("sessions");
+    /// let names = collection.create_indexes(vec![
+    ///     IndexModel::new(doc!{"user": 1, "created_at": -1}),
+    ///     IndexModel {
+    ///         expire_after_seconds: Some(3600),
+    ///         ..IndexModel::new(doc!{"created_at": 1})
+    ///     },
+    /// ])?;
+    /// assert_eq!(vec!["user_1_created_at_-1", "created_at_1"], names);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn create_indexes(&self, indexes: Vec<IndexModel>) -> Result<Vec<String>> {
         let mut error = BsoncError::empty();
         let reply = Bsonc::empty();
 
-        let bsonc_opts = opts.map_or_else(|| Ok(Bsonc::empty()), Update::into_mongoc)?;
+        let names = indexes.iter().map(IndexModel::name).collect();
+        let specs: Vec<Bson> = indexes
+            .iter()
+            .map(|index| Bson::Document(index.to_document()))
+            .collect();
+        let command = doc! {
+            "createIndexes": self.name(),
+            "indexes": specs,
+        };
+        let bsonc_opts = self.session_opts(Bsonc::empty())?;
 
         let success = unsafe {
-            bindings::mongoc_collection_update_many(
+            bindings::mongoc_collection_write_command_with_opts(
                 self.inner,
-                Bsonc::from_document(&selector)?.as_ptr(),
-                Bsonc::from_document(&update)?.as_ptr(),
+                Bsonc::from_document(&command)?.as_ptr(),
                 bsonc_opts.as_ptr(),
                 reply.as_mut_ptr(),
                 error.as_mut_ptr(),
@@ -788,15 +2290,14 @@ impl Collection for Collectionc {
         };
 
         if success {
-            reply.as_document()
+            Ok(names)
         } else {
             Err(error.into())
         }
     }
 
-    /// Finds docs the number of documents in a collection.
+    /// Lists the indexes on the collection, including the default `_id` index.
     ///
-    /// TODO: Add docs
     /// # Examples
     /// ```
     /// #[macro_use]
@@ -812,51 +2313,105 @@ impl Collection for Collectionc {
     ///
     /// let db = client.default_database();
     /// let collection = db.get_collection("test");
-    /// collection.insert_many(vec![
-    ///     doc!{"num": 1},
-    ///     doc!{"num": 2},
-    ///     doc!{"num": 3},
-    ///     doc!{"num": 4},
-    /// ])?;
+    /// collection.create_index(IndexModel { sparse: true, ..IndexModel::new(doc!{"tag": 1}) })?;
     ///
-    /// let maybe: Result<Vec<bson::Document>> = collection.aggregate_with_opts(doc!{"pipeline": [
-    /// {
-    ///     "$group": {
-    ///         "_id": null,
-    ///         "total": { "$sum": "$num" }
-    ///     }
-    /// }
-    /// ]}, None).collect();
-    ///
-    /// assert!(maybe.is_ok());
-    /// let records = maybe.unwrap();
-    /// let val = dbg!(records)[0].get_i32("total");
+    /// let indexes = collection.list_indexes()?;
+    /// assert_eq!(2, indexes.len());
+    /// let tag = indexes.iter().find(|index| index.name() == "tag_1").unwrap();
+    /// assert!(tag.sparse);
     ///
-    /// assert!(val.is_ok());
-    /// assert_eq!(10, val.unwrap());
     /// # db.destroy();
     /// # Ok(())
     /// # }
     /// ```
-    fn aggregate_with_opts(&self, pipeline: bson::Document, opts: Option<Aggregate>) -> Cursorc {
-        let bsonc_pipeline = Bsonc::from_document(&pipeline).expect("should be valid");
+    fn list_indexes(&self) -> Result<Vec<IndexModel>> {
+        let bsonc_opts = self.session_opts(Bsonc::empty())?;
 
-        let agg_opts = opts.unwrap_or_default();
-        let bsonc_opts = agg_opts.to_mongoc().expect("should be valid agg opts");
+        let cursor = Cursorc::from_ptr(unsafe {
+            bindings::mongoc_collection_find_indexes_with_opts(self.inner, bsonc_opts.as_ptr())
+        });
 
-        let read_pref = ReadPrefsc::default();
+        cursor
+            .map(|index| index.map(|index| IndexModel::from_document(&index)))
+            .collect()
+    }
 
-        let ptr = unsafe {
-            bindings::mongoc_collection_aggregate(
+    /// Drops the index with the given name.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// let name = collection.create_index(IndexModel::new(doc!{"tag": 1}))?;
+    /// collection.drop_index(&name)?;
+    /// assert_eq!(1, collection.list_indexes()?.len());
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn drop_index(&self, name: &str) -> Result<()> {
+        let mut error = BsoncError::empty();
+        let name = CString::new(name).map_err(MongoError::from)?;
+        let bsonc_opts = self.session_opts(Bsonc::empty())?;
+
+        let success = unsafe {
+            bindings::mongoc_collection_drop_index_with_opts(
                 self.inner,
-                agg_opts.query_flags.flags(),
-                bsonc_pipeline.as_ptr(),
+                name.as_ptr(),
                 bsonc_opts.as_ptr(),
-                read_pref.as_ptr(),
+                error.as_mut_ptr(),
             )
         };
 
-        Cursorc::from_ptr(ptr)
+        if success {
+            Ok(())
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Drops every index on the collection except the default `_id` index.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let collection = db.get_collection("test");
+    /// collection.create_indexes(vec![
+    ///     IndexModel::new(doc!{"a": 1}),
+    ///     IndexModel::new(doc!{"b": 1}),
+    /// ])?;
+    /// collection.drop_indexes()?;
+    /// assert_eq!(1, collection.list_indexes()?.len());
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn drop_indexes(&self) -> Result<()> {
+        self.drop_index("*")
     }
 
     /// Watches collection
@@ -901,18 +2456,18 @@ impl Collection for Collectionc {
     fn watch(
         &self,
         pipeline: Option<bson::Document>,
-        _opts: Option<bson::Document>,
+        opts: Option<ChangeStreamOptions>,
     ) -> Result<Self::ChangeStream> {
         let bson_pipeline = pipeline.map_or_else(Bsonc::empty, |o| {
             Bsonc::from_document(&o).expect("should be valid")
         });
-        let empty = Bsonc::empty();
+        let bsonc_opts = self.session_opts(opts.unwrap_or_default().into_mongoc()?)?;
 
         let inner = unsafe {
             bindings::mongoc_collection_watch(
                 self.inner,
                 bson_pipeline.as_mut_ptr(),
-                empty.as_mut_ptr(),
+                bsonc_opts.as_mut_ptr(),
             )
         };
 
@@ -0,0 +1,67 @@
+//! Description of a collection as returned by listCollections.
+
+use bson::Document;
+
+/// Kind of a listed collection.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CollectionType {
+    Collection,
+    View,
+    Timeseries,
+    /// Any type this crate does not know about
+    Other(String),
+}
+
+/// Name, type and options of a collection in a database.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CollectionSpec {
+    pub name: String,
+    pub collection_type: CollectionType,
+    /// Options the collection was created with, e.g. `capped` or `validator`
+    pub options: Document,
+    /// Server information such as `readOnly` and `uuid`
+    pub info: Document,
+}
+
+impl CollectionSpec {
+    /// Reads a collection specification as returned by listCollections.
+    pub(crate) fn from_document(d: &Document) -> Self {
+        let collection_type = match d.get_str("type").unwrap_or("collection") {
+            "collection" => CollectionType::Collection,
+            "view" => CollectionType::View,
+            "timeseries" => CollectionType::Timeseries,
+            other => CollectionType::Other(other.to_string()),
+        };
+
+        CollectionSpec {
+            name: d.get_str("name").unwrap_or_default().to_string(),
+            collection_type,
+            options: d.get_document("options").cloned().unwrap_or_default(),
+            info: d.get_document("info").cloned().unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CollectionSpec, CollectionType};
+
+    #[test]
+    fn test_collection_spec_from_document() {
+        let spec = CollectionSpec::from_document(&doc! {
+            "name": "recent",
+            "type": "view",
+            "options": { "viewOn": "events", "pipeline": [] },
+            "info": { "readOnly": true },
+        });
+
+        assert_eq!("recent", spec.name);
+        assert_eq!(CollectionType::View, spec.collection_type);
+        assert_eq!(Ok("events"), spec.options.get_str("viewOn"));
+        assert_eq!(Ok(true), spec.info.get_bool("readOnly"));
+
+        let spec = CollectionSpec::from_document(&doc! { "name": "events" });
+        assert_eq!(CollectionType::Collection, spec.collection_type);
+        assert!(spec.options.is_empty());
+    }
+}
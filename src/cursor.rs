@@ -3,27 +3,21 @@ use crate::{
     bsonc::Bsonc,
     error::{BsoncError, Result},
     host::{Host, Hostc},
+    worker::{Fetched, Worker},
 };
 
 use futures::stream::Stream;
 use std::{
-    ptr,
     pin::Pin,
-    sync::{Arc,Mutex},
-    task::{Context, Poll, Waker},
-    thread,
-    time::Duration
+    ptr,
+    task::{Context, Poll},
 };
 
-#[derive(Debug)]
-struct SharedState {
-    waker: Option<Waker>
-}
-
 #[derive(Debug)]
 pub struct Cursorc {
     inner: *mut bindings::mongoc_cursor_t,
-    shared_state: Arc<Mutex<SharedState>>
+    /// Why the cursor could not be created, returned by the first call to `next`
+    failed: Option<failure::Error>,
 }
 
 pub trait Cursor {
@@ -33,30 +27,43 @@ pub trait Cursor {
 
 impl Cursorc {
     pub fn from_ptr(inner: *mut bindings::mongoc_cursor_t) -> Self {
-        let shared_state = Arc::new(Mutex::new(SharedState{ waker: None }));
-
-        let thread_shared_state = shared_state.clone();
+        Cursorc {
+            inner,
+            failed: None,
+        }
+    }
 
-        let duration = Duration::from_nanos(100);
-        thread::spawn(move || {
-            thread::sleep(duration);
-            let mut shared_state = thread_shared_state.lock().unwrap();
+    /// A cursor without documents that returns the error once.
+    pub(crate) fn from_error(error: failure::Error) -> Self {
+        let mut cursor = Cursorc::from_ptr(ptr::null_mut());
+        cursor.failed = Some(error);
+        cursor
+    }
 
-            if let Some(waker) = shared_state.waker.take() {
-                waker.wake();
-            }
-        });
+    /// Whether the cursor may return more documents.  A tailable cursor without new
+    /// documents is still alive, it is false once the cursor is exhausted or failed.
+    pub fn more(&self) -> bool {
+        if self.inner.is_null() {
+            return false;
+        }
 
-        Cursorc { inner, shared_state }
+        unsafe { bindings::mongoc_cursor_more(self.inner) }
     }
 
     pub fn get_error(&self) -> Option<BsoncError> {
-        assert!(!self.inner.is_null());
+        if self.inner.is_null() {
+            // The error the cursor was created with is returned by `next`.
+            return None;
+        }
+
+        Cursorc::error_of(self.inner)
+    }
 
+    fn error_of(inner: *mut bindings::mongoc_cursor_t) -> Option<BsoncError> {
         let mut error = BsoncError::empty();
 
         unsafe {
-            bindings::mongoc_cursor_error(self.inner, error.as_mut_ptr());
+            bindings::mongoc_cursor_error(inner, error.as_mut_ptr());
         }
 
         if error.is_empty() {
@@ -65,13 +72,33 @@ impl Cursorc {
             Some(error)
         }
     }
+
+    /// The next document, `Empty` while a tailable cursor waits for new documents.
+    fn next_of(inner: *mut bindings::mongoc_cursor_t) -> Fetched<Result<bson::Document>> {
+        let mut bson_ptr: *const bindings::bson_t = ptr::null_mut();
+
+        let success = unsafe { bindings::mongoc_cursor_next(inner, &mut bson_ptr) };
+
+        if let Some(err) = Cursorc::error_of(inner) {
+            Fetched::Item(Err(err.into()))
+        } else if success {
+            let bsonc = Bsonc::from_ptr(bson_ptr);
+            Fetched::Item(bsonc.as_document())
+        } else if unsafe { bindings::mongoc_cursor_more(inner) } {
+            Fetched::Empty
+        } else {
+            Fetched::Done
+        }
+    }
 }
 
 impl Cursor for Cursorc {
     type Host = Hostc;
 
     fn get_hosts(&self) -> Option<Vec<Self::Host>> {
-        assert!(!self.inner.is_null());
+        if self.inner.is_null() {
+            return None;
+        }
 
         unsafe {
             let ptr = ptr::null_mut();
@@ -86,43 +113,118 @@ impl Cursor for Cursorc {
     }
 }
 
+/// Blocks until the next document.  To fetch in async code use `PooledCollection::find`
+/// or `PooledCollection::aggregate`, which fetch on a worker thread.
 impl Iterator for Cursorc {
     type Item = Result<bson::Document>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut bson_ptr: *const bindings::bson_t = ptr::null_mut();
-
-        let success = unsafe { bindings::mongoc_cursor_next(self.inner, &mut bson_ptr) };
+        if self.inner.is_null() {
+            return self.failed.take().map(Err);
+        }
 
-        if let Some(err) = self.get_error() {
-            Some(Err(err.into()))
-        } else if success {
-            let bsonc = Bsonc::from_ptr(bson_ptr);
-            Some(bsonc.as_document())
-        } else {
-            None
+        match Cursorc::next_of(self.inner) {
+            Fetched::Item(doc) => Some(doc),
+            Fetched::Empty | Fetched::Done => None,
         }
     }
 }
 
-impl Stream for Cursorc {
-    type Item = Result<bson::Document>;
+/// A cursor fetching its documents on a worker thread, so polling never blocks the
+/// executor.  The thread owns the pooled client the cursor was opened with, the client
+/// goes back to the pool after the cursor is dropped, as soon as the thread's current
+/// fetch returns.
+///
+/// The cursor ends after the first error, which is also how an error opening the cursor
+/// is returned.
+///
+/// # Examples
+/// ```
+/// #[macro_use]
+/// extern crate bson;
+/// use futures::{executor::block_on, stream::StreamExt};
+/// use mongo_leaf::prelude::*;
+/// use std::{env, sync::Arc};
+///
+/// # fn main() -> Result<()> {
+/// env::set_var("MONGODB_URI","mongodb://standard");
+/// let builder = Builder::new();
+/// let pool = Arc::new(builder.random_database_connect()?);
+/// let collection = pool.pop_pooled().default_database().collection("test");
+/// collection.run(|collection| {
+///     collection.insert_many(vec![doc!{"name": "omg"}, doc!{"name": "foo"}])
+/// })?;
+///
+/// let docs: Vec<Result<bson::Document>> =
+///     block_on(StreamExt::collect(collection.find(doc!{}, None)));
+/// assert_eq!(2, docs.len());
+///
+/// # pool.pop().default_database().destroy();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PooledCursor {
+    worker: Worker<Result<bson::Document>>,
+    /// Whether the worker reported the end of the cursor
+    exhausted: bool,
+}
 
-    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+impl PooledCursor {
+    /// Opens the cursor on the worker thread, `open` returns it along with the pooled
+    /// handle it was opened with, which is dropped after the cursor.
+    pub(crate) fn open<H, O>(open: O) -> Self
+    where
+        O: FnOnce() -> Result<(Cursorc, H)> + Send + 'static,
+    {
+        PooledCursor {
+            worker: Worker::open(open, |(cursor, _)| match cursor.failed.take() {
+                Some(err) => Fetched::Item(Err(err)),
+                None => Cursorc::next_of(cursor.inner),
+            }),
+            exhausted: false,
+        }
+    }
 
-        let mut bson_ptr: *const bindings::bson_t = ptr::null_mut();
+    /// Whether the cursor may return more documents, see `Cursorc::more`.
+    pub fn more(&self) -> bool {
+        !self.exhausted
+    }
+}
 
-        let success = unsafe { bindings::mongoc_cursor_next(self.inner, &mut bson_ptr) };
+/// Blocks until the next document, `None` when a tailable cursor has no new documents.
+impl Iterator for PooledCursor {
+    type Item = Result<bson::Document>;
 
-        if let Some(err) = self.get_error() {
-            Poll::Ready(Some(Err(err.into())))
-        } else if success {
-            let bsonc = Bsonc::from_ptr(bson_ptr);
-            Poll::Ready(Some(bsonc.as_document()))
-        } else {
-            Poll::Pending
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.worker.wait() {
+            Fetched::Item(doc) => Some(doc),
+            Fetched::Empty => None,
+            Fetched::Done => {
+                self.exhausted = true;
+                None
+            }
         }
+    }
+}
+
+impl Stream for PooledCursor {
+    type Item = Result<bson::Document>;
 
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            return match this.worker.poll(cx) {
+                Poll::Ready(Fetched::Item(doc)) => Poll::Ready(Some(doc)),
+                Poll::Ready(Fetched::Empty) => continue,
+                Poll::Ready(Fetched::Done) => {
+                    this.exhausted = true;
+                    Poll::Ready(None)
+                }
+                Poll::Pending => Poll::Pending,
+            };
+        }
     }
 }
 
@@ -130,11 +232,9 @@ impl Drop for Cursorc {
     fn drop(&mut self) {
         if !self.inner.is_null() {
             unsafe {
-                dbg!("cursor drop start");
-                bindings::mongoc_cursor_destroy(dbg!(self.inner));
-                self.inner = ptr::null_mut();
-                dbg!("cursor drop done");
+                bindings::mongoc_cursor_destroy(self.inner);
             }
+            self.inner = ptr::null_mut();
         }
     }
 }
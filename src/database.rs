@@ -1,18 +1,29 @@
 use crate::{
     bindings,
+    bsonc::Bsonc,
+    change_stream::{ChangeStream, ChangeStreamc},
     collection::{Collection, Collectionc},
+    collection_spec::CollectionSpec,
+    cursor::{Cursor, Cursorc},
     error::{BsoncError, Result},
+    options::{ChangeStreamOptions, CreateCollectionOptions, RunCommand},
+    read_concern::{ReadConcern, ReadConcernc},
+    read_prefs::{ReadPrefs, ReadPrefsc},
+    write_concern::{WriteConcern, WriteConcernc},
 };
 use std::ffi::{CStr, CString};
 use std::ptr;
 
 #[derive(Debug)]
 pub struct Databasec {
+    client: *mut bindings::mongoc_client_t,
     inner: *mut bindings::mongoc_database_t,
 }
 
 pub trait Database {
     type Collection: Collection;
+    type Cursor: Cursor;
+    type ChangeStream: ChangeStream;
 
     fn name(&self) -> String;
     fn as_mut_ptr(&self) -> *mut bindings::mongoc_database_t {
@@ -21,16 +32,107 @@ pub trait Database {
 
     fn destroy(&self) -> Result<bool>;
     fn get_collection(&self, name: impl Into<String>) -> Self::Collection;
+
+    fn run_command(
+        &self,
+        command: bson::Document,
+        opts: Option<RunCommand>,
+    ) -> Result<bson::Document>;
+    fn run_read_command(
+        &self,
+        command: bson::Document,
+        opts: Option<RunCommand>,
+    ) -> Result<bson::Document>;
+    fn run_write_command(
+        &self,
+        command: bson::Document,
+        opts: Option<RunCommand>,
+    ) -> Result<bson::Document>;
+    fn run_cursor_command(
+        &self,
+        command: bson::Document,
+        opts: Option<RunCommand>,
+    ) -> Result<Self::Cursor>;
+
+    fn list_collections(&self, filter: Option<bson::Document>) -> Result<Vec<CollectionSpec>>;
+    fn list_collection_names(&self, filter: Option<bson::Document>) -> Result<Vec<String>>;
+    fn has_collection(&self, name: impl Into<String>) -> Result<bool>;
+    fn create_collection(
+        &self,
+        name: impl Into<String>,
+        opts: Option<CreateCollectionOptions>,
+    ) -> Result<Self::Collection>;
+
+    fn read_prefs(&self) -> ReadPrefsc;
+    fn set_read_prefs(&self, read_prefs: &ReadPrefsc);
+    fn read_concern(&self) -> ReadConcernc;
+    fn set_read_concern(&self, read_concern: &ReadConcernc);
+    fn write_concern(&self) -> WriteConcernc;
+    fn set_write_concern(&self, write_concern: &WriteConcernc);
+
+    fn watch(
+        &self,
+        pipeline: Option<bson::Document>,
+        opts: Option<ChangeStreamOptions>,
+    ) -> Result<Self::ChangeStream>;
 }
 
 impl Databasec {
-    pub fn new(inner: *mut bindings::mongoc_database_t) -> Self {
-        Databasec { inner }
+    pub fn new(
+        client: *mut bindings::mongoc_client_t,
+        inner: *mut bindings::mongoc_database_t,
+    ) -> Self {
+        Databasec { client, inner }
+    }
+
+    /// Selects a server for reading with the read prefs, the id is needed to fetch the
+    /// later batches of a cursor from the same server.
+    fn select_server(&self, read_prefs: Option<&ReadPrefsc>) -> Result<u32> {
+        let mut error = BsoncError::empty();
+
+        unsafe {
+            let description = bindings::mongoc_client_select_server(
+                self.client,
+                false,
+                read_prefs.map_or(ptr::null(), ReadPrefs::as_ptr),
+                error.as_mut_ptr(),
+            );
+            if description.is_null() {
+                return Err(error.into());
+            }
+
+            let server_id = bindings::mongoc_server_description_id(description);
+            bindings::mongoc_server_description_destroy(description);
+            Ok(server_id)
+        }
+    }
+
+    /// Runs listCollections and reads every returned specification.
+    fn find_collections(
+        &self,
+        filter: Option<bson::Document>,
+        name_only: bool,
+    ) -> Result<Vec<bson::Document>> {
+        let mut opts = doc! { "nameOnly": name_only };
+        if let Some(filter) = filter {
+            opts.insert("filter", filter);
+        }
+
+        let ptr = unsafe {
+            bindings::mongoc_database_find_collections_with_opts(
+                self.inner,
+                Bsonc::from_document(&opts)?.as_ptr(),
+            )
+        };
+
+        Cursorc::from_ptr(ptr).collect()
     }
 }
 
 impl Database for Databasec {
     type Collection = Collectionc;
+    type Cursor = Cursorc;
+    type ChangeStream = ChangeStreamc;
 
     fn name(&self) -> String {
         unsafe {
@@ -53,6 +155,296 @@ impl Database for Databasec {
         Collectionc::from_ptr(ptr)
     }
 
+    /// Runs a command on the database and returns the full reply.  Only the read prefs,
+    /// session, server and concerns given in the opts are used, nothing is inherited.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let reply = db.run_command(doc!{"ping": 1}, None)?;
+    /// assert_eq!(Ok(1.0), reply.get_f64("ok"));
+    ///
+    /// db.get_collection("test").insert_one(doc!{"name": "omg"})?;
+    /// let opts = RunCommand {
+    ///     read_concern: Some(ReadConcernc::new(ReadConcernLevel::Majority)?),
+    ///     ..Default::default()
+    /// };
+    /// let reply = db.run_read_command(doc!{"count": "test"}, Some(opts))?;
+    /// assert_eq!(Ok(1), reply.get_i32("n"));
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn run_command(
+        &self,
+        command: bson::Document,
+        opts: Option<RunCommand>,
+    ) -> Result<bson::Document> {
+        let (opts, read_prefs) = opts.unwrap_or_default().into_mongoc()?;
+        let mut error = BsoncError::empty();
+        let reply = Bsonc::empty();
+
+        let success = unsafe {
+            bindings::mongoc_database_command_with_opts(
+                self.inner,
+                Bsonc::from_document(&command)?.as_ptr(),
+                read_prefs.as_ref().map_or(ptr::null(), ReadPrefs::as_ptr),
+                opts.as_ptr(),
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if success {
+            reply.as_document()
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Runs a command that reads, the read prefs and read concern of the database are
+    /// used unless given in the opts.
+    fn run_read_command(
+        &self,
+        command: bson::Document,
+        opts: Option<RunCommand>,
+    ) -> Result<bson::Document> {
+        let (opts, read_prefs) = opts.unwrap_or_default().into_mongoc()?;
+        let mut error = BsoncError::empty();
+        let reply = Bsonc::empty();
+
+        let success = unsafe {
+            bindings::mongoc_database_read_command_with_opts(
+                self.inner,
+                Bsonc::from_document(&command)?.as_ptr(),
+                read_prefs.as_ref().map_or(ptr::null(), ReadPrefs::as_ptr),
+                opts.as_ptr(),
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if success {
+            reply.as_document()
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Runs a command that writes on the primary, the write concern of the database is
+    /// used unless given in the opts.
+    fn run_write_command(
+        &self,
+        command: bson::Document,
+        opts: Option<RunCommand>,
+    ) -> Result<bson::Document> {
+        let (opts, _) = opts.unwrap_or_default().into_mongoc()?;
+        let mut error = BsoncError::empty();
+        let reply = Bsonc::empty();
+
+        let success = unsafe {
+            bindings::mongoc_database_write_command_with_opts(
+                self.inner,
+                Bsonc::from_document(&command)?.as_ptr(),
+                opts.as_ptr(),
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if success {
+            reply.as_document()
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Runs a command replying with a cursor, such as listCollections or aggregate, and
+    /// iterates the documents of the cursor.  The later batches are fetched from the
+    /// server that ran the command, selected with the read prefs unless the opts give a
+    /// server id.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// db.create_collection("events", None)?;
+    ///
+    /// let cursor = db.run_cursor_command(doc!{"listCollections": 1, "nameOnly": true}, None)?;
+    /// let names: Result<Vec<bson::Document>> = cursor.collect();
+    /// assert_eq!(Ok("events"), names?[0].get_str("name"));
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn run_cursor_command(
+        &self,
+        command: bson::Document,
+        opts: Option<RunCommand>,
+    ) -> Result<Self::Cursor> {
+        let mut opts = opts.unwrap_or_default();
+        let server_id = match opts.server_id {
+            Some(server_id) => server_id,
+            None => self.select_server(opts.read_prefs.as_ref())?,
+        };
+        opts.server_id = Some(server_id);
+
+        let mut cursor_opts = Bsonc::from_document(&doc! { "serverId": server_id as i32 })?;
+        if let Some(session) = opts.session {
+            session.append(&mut cursor_opts)?;
+        }
+
+        let (opts, read_prefs) = opts.into_mongoc()?;
+        let mut error = BsoncError::empty();
+        let reply = Bsonc::empty();
+
+        let success = unsafe {
+            bindings::mongoc_database_read_command_with_opts(
+                self.inner,
+                Bsonc::from_document(&command)?.as_ptr(),
+                read_prefs.as_ref().map_or(ptr::null(), ReadPrefs::as_ptr),
+                opts.as_ptr(),
+                reply.as_mut_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+        if !success {
+            return Err(error.into());
+        }
+
+        // The cursor takes ownership of the reply it is created from.
+        let cursor = Cursorc::from_ptr(unsafe {
+            bindings::mongoc_cursor_new_from_command_reply_with_opts(
+                self.client,
+                bindings::bson_copy(reply.as_ptr()),
+                cursor_opts.as_ptr(),
+            )
+        });
+
+        match cursor.get_error() {
+            Some(error) => Err(error.into()),
+            None => Ok(cursor),
+        }
+    }
+
+    /// Lists the collections and views of the database, optionally only those matching
+    /// the filter, e.g. `{"type": "view"}`.
+    ///
+    /// # Examples
+    /// ```
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://standard");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let opts = CreateCollectionOptions { capped: true, size: Some(4096), ..Default::default() };
+    /// db.create_collection("log", Some(opts))?;
+    /// db.create_collection("events", None)?;
+    ///
+    /// let specs = db.list_collections(Some(doc!{"name": "log"}))?;
+    /// assert_eq!(1, specs.len());
+    /// assert_eq!(CollectionType::Collection, specs[0].collection_type);
+    /// assert_eq!(Ok(true), specs[0].options.get_bool("capped"));
+    ///
+    /// let mut names = db.list_collection_names(None)?;
+    /// names.sort();
+    /// assert_eq!(vec!["events".to_string(), "log".to_string()], names);
+    /// assert!(db.has_collection("events")?);
+    /// assert!(!db.has_collection("missing")?);
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn list_collections(&self, filter: Option<bson::Document>) -> Result<Vec<CollectionSpec>> {
+        Ok(self
+            .find_collections(filter, false)?
+            .iter()
+            .map(CollectionSpec::from_document)
+            .collect())
+    }
+
+    /// Lists the names of the collections and views of the database.
+    fn list_collection_names(&self, filter: Option<bson::Document>) -> Result<Vec<String>> {
+        Ok(self
+            .find_collections(filter, true)?
+            .iter()
+            .filter_map(|d| d.get_str("name").ok().map(str::to_string))
+            .collect())
+    }
+
+    /// Checks if the database has a collection or view with the given name.
+    fn has_collection(&self, name: impl Into<String>) -> Result<bool> {
+        let name = CString::new(name.into())?;
+        let mut error = BsoncError::empty();
+
+        let exists = unsafe {
+            bindings::mongoc_database_has_collection(self.inner, name.as_ptr(), error.as_mut_ptr())
+        };
+
+        if error.is_empty() {
+            Ok(exists)
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Creates a collection, an error is returned if it already exists.
+    fn create_collection(
+        &self,
+        name: impl Into<String>,
+        opts: Option<CreateCollectionOptions>,
+    ) -> Result<Self::Collection> {
+        let name = CString::new(name.into())?;
+        let opts = opts.unwrap_or_default().into_mongoc()?;
+        let mut error = BsoncError::empty();
+
+        let ptr = unsafe {
+            bindings::mongoc_database_create_collection(
+                self.inner,
+                name.as_ptr(),
+                opts.as_ptr(),
+                error.as_mut_ptr(),
+            )
+        };
+
+        if ptr.is_null() {
+            Err(error.into())
+        } else {
+            Ok(Collectionc::from_ptr(ptr))
+        }
+    }
+
     fn destroy(&self) -> Result<bool> {
         let mut error = BsoncError::empty();
         let success = unsafe { bindings::mongoc_database_drop(self.inner, error.as_mut_ptr()) };
@@ -63,6 +455,95 @@ impl Database for Databasec {
             Err(error.into())
         }
     }
+
+    /// The read prefs used by operations on the database and its collections that don't set their own.
+    fn read_prefs(&self) -> ReadPrefsc {
+        ReadPrefsc::copy_from_ptr(unsafe { bindings::mongoc_database_get_read_prefs(self.inner) })
+    }
+
+    /// Sets the read prefs inherited by operations on the database and its collections.
+    fn set_read_prefs(&self, read_prefs: &ReadPrefsc) {
+        unsafe { bindings::mongoc_database_set_read_prefs(self.inner, read_prefs.as_ptr()) }
+    }
+
+    /// The read concern used by operations on the database and its collections that don't set their own.
+    fn read_concern(&self) -> ReadConcernc {
+        ReadConcernc::copy_from_ptr(unsafe {
+            bindings::mongoc_database_get_read_concern(self.inner)
+        })
+    }
+
+    /// Sets the read concern inherited by operations on the database and its collections.
+    fn set_read_concern(&self, read_concern: &ReadConcernc) {
+        unsafe { bindings::mongoc_database_set_read_concern(self.inner, read_concern.as_ptr()) }
+    }
+
+    /// The write concern used by operations on the database and its collections that don't set their own.
+    fn write_concern(&self) -> WriteConcernc {
+        WriteConcernc::copy_from_ptr(unsafe {
+            bindings::mongoc_database_get_write_concern(self.inner)
+        })
+    }
+
+    /// Sets the write concern inherited by operations on the database and its collections.
+    fn set_write_concern(&self, write_concern: &WriteConcernc) {
+        unsafe { bindings::mongoc_database_set_write_concern(self.inner, write_concern.as_ptr()) }
+    }
+
+    /// Watches every collection of the database with a single change stream.
+    ///
+    /// NOTE:  Change streams need a replica set or a sharded cluster.
+    /// # Examples
+    /// ```no_run
+    /// #[macro_use]
+    /// extern crate bson;
+    /// use mongo_leaf::prelude::*;
+    /// use std::env;
+    ///
+    /// # fn main() -> Result<()> {
+    /// env::set_var("MONGODB_URI","mongodb://repl/?replicaSet=rs0");
+    /// let builder = Builder::new();
+    /// let pool = builder.random_database_connect()?;
+    /// let mut client = pool.pop();
+    ///
+    /// let db = client.default_database();
+    /// let pipeline = doc!{"pipeline": [{"$match": {"operationType": "insert"}}]};
+    /// let mut stream = db.watch(Some(pipeline), None)?;
+    ///
+    /// db.get_collection("users").insert_one(doc!{"name": "first"})?;
+    /// db.get_collection("orders").insert_one(doc!{"total": 10})?;
+    ///
+    /// let change = stream.next().unwrap()?;
+    /// assert_eq!(Ok("users"), change.get_document("ns")?.get_str("coll"));
+    /// let change = stream.next().unwrap()?;
+    /// assert_eq!(Ok("orders"), change.get_document("ns")?.get_str("coll"));
+    ///
+    /// # db.destroy();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn watch(
+        &self,
+        pipeline: Option<bson::Document>,
+        opts: Option<ChangeStreamOptions>,
+    ) -> Result<Self::ChangeStream> {
+        let bson_pipeline = pipeline.map_or_else(Bsonc::empty, |o| {
+            Bsonc::from_document(&o).expect("should be valid")
+        });
+        let bsonc_opts = opts.unwrap_or_default().into_mongoc()?;
+
+        let inner = unsafe {
+            bindings::mongoc_database_watch(self.inner, bson_pipeline.as_ptr(), bsonc_opts.as_ptr())
+        };
+
+        let change_stream = ChangeStreamc::from_ptr(inner);
+
+        if let Some(error) = change_stream.get_error() {
+            return Err(error.into());
+        }
+
+        Ok(change_stream)
+    }
 }
 
 impl Drop for Databasec {
@@ -0,0 +1,48 @@
+//! Description of a database as returned by listDatabases.
+
+use crate::results::reply_count;
+use bson::Document;
+
+/// Name and size of a database on the server.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DatabaseSpec {
+    pub name: String,
+    /// Size of the database files in bytes, 0 when only names were listed
+    pub size_on_disk: i64,
+    /// Whether the database holds no data
+    pub empty: bool,
+}
+
+impl DatabaseSpec {
+    /// Reads a database entry as returned by listDatabases.
+    pub(crate) fn from_document(d: &Document) -> Self {
+        DatabaseSpec {
+            name: d.get_str("name").unwrap_or_default().to_string(),
+            size_on_disk: reply_count(d, "sizeOnDisk"),
+            empty: d.get_bool("empty").unwrap_or(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DatabaseSpec;
+
+    #[test]
+    fn test_database_spec_from_document() {
+        let spec = DatabaseSpec::from_document(&doc! {
+            "name": "tenant_42",
+            "sizeOnDisk": 8192.0,
+            "empty": false,
+        });
+
+        assert_eq!(
+            DatabaseSpec {
+                name: "tenant_42".to_string(),
+                size_on_disk: 8192,
+                empty: false,
+            },
+            spec
+        );
+    }
+}
@@ -1,10 +1,12 @@
-use crate::{bindings, read_concern::ReadConcernLevel};
+use crate::{bindings, read_concern::ReadConcernLevel, results::BulkWriteResult};
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::error;
 use std::ffi::CStr;
 use std::fmt;
+use std::time::Duration;
 
-use bson::{DecoderError, Document, EncoderError, ValueAccessError};
+use bson::{oid, Bson, DecoderError, Document, EncoderError, ValueAccessError};
 use failure::{Backtrace, Context, Fail};
 use std::ffi::NulError;
 
@@ -26,11 +28,15 @@ pub enum MongoError {
     Encoder(EncoderError),
     /// Error accessing a value on a Bson document.
     ValueAccessError(ValueAccessError),
+    /// Error generating an ObjectId.
+    ObjectId(oid::Error),
     /// Invalid params error that can be reported by the underlying C driver.
     InvalidParams(InvalidParamsError),
     // from CString::new(db)
     Nul(NulError),
     InvalidReadConcern(ReadConcernLevel),
+    /// Replacement document contains update operators.
+    InvalidReplacement(String),
     PathDoesNotExist(String),
     SSLNoPEMFile,
     SSLNoCAFile,
@@ -43,9 +49,13 @@ impl fmt::Display for MongoError {
             MongoError::Encoder(ref err) => write!(f, "{}", err),
             MongoError::Decoder(ref err) => write!(f, "{}", err),
             MongoError::ValueAccessError(ref err) => write!(f, "{}", err),
+            MongoError::ObjectId(ref err) => write!(f, "{}", err),
             MongoError::InvalidParams(ref err) => write!(f, "{}", err),
             MongoError::Nul(ref err) => write!(f, "{}", err),
             MongoError::InvalidReadConcern(ref err) => write!(f, "Invalid Read concern of {}", err),
+            MongoError::InvalidReplacement(ref key) => {
+                write!(f, "Replacement document can not contain operator {}", key)
+            }
             MongoError::PathDoesNotExist(ref err) => write!(f, "Path not found {}", err),
             MongoError::SSLNoPEMFile => write!(f, "No PEM File given"),
             MongoError::SSLNoCAFile => write!(f, "No CA File given"),
@@ -60,11 +70,17 @@ impl fmt::Debug for MongoError {
             MongoError::Decoder(ref err) => write!(f, "MongoError ({:?})", err),
             MongoError::Encoder(ref err) => write!(f, "MongoError ({:?})", err),
             MongoError::ValueAccessError(ref err) => write!(f, "MongoError ({:?})", err),
+            MongoError::ObjectId(ref err) => write!(f, "MongoError ({:?})", err),
             MongoError::InvalidParams(ref err) => write!(f, "MongoError ({:?})", err),
             MongoError::Nul(ref err) => write!(f, "MongoError ({:?})", err),
             MongoError::InvalidReadConcern(ref err) => {
                 write!(f, "MongoError (Invalid Read concern of {:?})", err)
             }
+            MongoError::InvalidReplacement(ref key) => write!(
+                f,
+                "MongoError (Replacement document can not contain operator {:?})",
+                key
+            ),
             MongoError::PathDoesNotExist(ref err) => {
                 write!(f, "SSL Options (Path not found {:?})", err)
             }
@@ -122,6 +138,12 @@ impl From<ValueAccessError> for MongoError {
     }
 }
 
+impl From<oid::Error> for MongoError {
+    fn from(error: oid::Error) -> MongoError {
+        MongoError::ObjectId(error)
+    }
+}
+
 impl From<NulError> for MongoError {
     fn from(error: NulError) -> MongoError {
         MongoError::Nul(error)
@@ -458,6 +480,27 @@ impl From<InvalidParamsError> for MongoError {
     }
 }
 
+/// No client of the pool was pushed back within the timeout of `ClientPool::pop_timeout`.
+#[derive(Debug)]
+pub struct PoolExhaustedError {
+    pub timeout: Duration,
+    /// The maxPoolSize of the pool
+    pub max_size: usize,
+}
+
+// Not derived, the impls generated by `failure_derive` trip the non-local impl lint.
+impl Fail for PoolExhaustedError {}
+
+impl fmt::Display for PoolExhaustedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "All {} clients of the pool in use for {:?}",
+            self.max_size, self.timeout
+        )
+    }
+}
+
 /// Error returned by a bulk operation that includes a report in the reply document.
 #[derive(Fail, Debug)]
 #[fail(display = "Bulk operation error {}", error)]
@@ -466,11 +509,56 @@ pub struct BulkOperationError {
     pub error: MongoError,
     /// Error report
     pub reply: Document,
+    /// Writes that failed, in the order they were reported by the server
+    pub write_errors: Vec<WriteError>,
+    /// What was written before the failure, with the `_id` of the applied inserts
+    pub result: BulkWriteResult,
+}
+
+/// A single failed write in a bulk operation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WriteError {
+    /// Index of the failed model in the bulk operation
+    pub index: usize,
+    /// Server error code
+    pub code: i32,
+    /// Server error message
+    pub message: String,
+}
+
+impl BulkOperationError {
+    pub(crate) fn new(error: BsoncError, reply: Document) -> Self {
+        let write_errors = match reply.get_array("writeErrors") {
+            Ok(errors) => errors
+                .iter()
+                .filter_map(|error| match error {
+                    Bson::Document(error) => Some(WriteError {
+                        index: error.get_i32("index").unwrap_or_default() as usize,
+                        code: error.get_i32("code").unwrap_or_default(),
+                        message: error.get_str("errmsg").unwrap_or_default().to_string(),
+                    }),
+                    _ => None,
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        BulkOperationError {
+            error: error.into(),
+            result: BulkWriteResult::from_reply(&reply, HashMap::new()),
+            reply,
+            write_errors,
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{BsoncError, MongoErrorCode, MongoErrorDomain};
+    use super::{
+        BsoncError, BulkOperationError, MongoErrorCode, MongoErrorDomain, PoolExhaustedError,
+        WriteError,
+    };
+    use std::time::Duration;
 
     #[test]
     fn test_bson_error_empty() {
@@ -498,4 +586,44 @@ mod tests {
         error.as_mut_ptr().code = 1;
         assert_eq!(MongoErrorCode::StreamInvalidType, error.code());
     }
+
+    #[test]
+    fn test_bulk_operation_error_write_errors() {
+        let reply = doc! {
+            "nInserted": 1,
+            "writeErrors": [
+                { "index": 1, "code": 11000, "errmsg": "E11000 duplicate key error" },
+            ],
+        };
+        let error = BulkOperationError::new(BsoncError::empty(), reply);
+        assert_eq!(
+            vec![WriteError {
+                index: 1,
+                code: 11000,
+                message: "E11000 duplicate key error".to_string(),
+            }],
+            error.write_errors
+        );
+        assert_eq!(1, error.result.inserted);
+    }
+
+    #[test]
+    fn test_pool_exhausted_error() {
+        let error: failure::Error = PoolExhaustedError {
+            timeout: Duration::from_millis(250),
+            max_size: 10,
+        }
+        .into();
+
+        assert_eq!(
+            "All 10 clients of the pool in use for 250ms",
+            error.to_string()
+        );
+        assert_eq!(
+            Some(10),
+            error
+                .downcast_ref::<PoolExhaustedError>()
+                .map(|error| error.max_size)
+        );
+    }
 }
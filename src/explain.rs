@@ -0,0 +1,151 @@
+//! Query plans reported by the explain command.
+
+use crate::results::reply_count;
+use bson::{Bson, Document};
+
+/// How much detail the explain command reports.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExplainVerbosity {
+    /// Only the plan chosen by the query planner, the query is not run
+    QueryPlanner,
+    /// Runs the winning plan and reports its execution statistics
+    ExecutionStats,
+    /// Also reports the statistics gathered for the rejected plans
+    AllPlansExecution,
+}
+
+impl ExplainVerbosity {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            ExplainVerbosity::QueryPlanner => "queryPlanner",
+            ExplainVerbosity::ExecutionStats => "executionStats",
+            ExplainVerbosity::AllPlansExecution => "allPlansExecution",
+        }
+    }
+}
+
+/// Summary of an explain reply.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExplainResult {
+    /// The plan the query planner picked
+    pub winning_plan: Option<Document>,
+    /// Names of the indexes used by the winning plan
+    pub indexes_used: Vec<String>,
+    /// Whether the winning plan scans the whole collection
+    pub collection_scan: bool,
+    /// Documents examined, only reported with execution stats
+    pub docs_examined: Option<i64>,
+    /// Index keys examined, only reported with execution stats
+    pub keys_examined: Option<i64>,
+    /// Documents returned, only reported with execution stats
+    pub docs_returned: Option<i64>,
+    /// The full reply of the explain command
+    pub reply: Document,
+}
+
+impl ExplainResult {
+    pub(crate) fn from_reply(reply: Document) -> Self {
+        // Aggregations report the plan of their first stage under `$cursor`.
+        let root = match reply.get_array("stages") {
+            Ok(stages) => match stages.first() {
+                Some(Bson::Document(stage)) => stage.get_document("$cursor").ok(),
+                _ => None,
+            },
+            Err(_) => None,
+        }
+        .unwrap_or(&reply);
+
+        let winning_plan = root
+            .get_document("queryPlanner")
+            .and_then(|planner| planner.get_document("winningPlan"))
+            .ok()
+            .cloned();
+
+        let mut stages = Vec::new();
+        let mut indexes_used = Vec::new();
+        if let Some(ref plan) = winning_plan {
+            walk_plan(plan, &mut stages, &mut indexes_used);
+        }
+
+        let stats = root.get_document("executionStats").ok();
+        let stat = |key| stats.map(|stats| reply_count(stats, key));
+
+        ExplainResult {
+            collection_scan: stages.iter().any(|stage| stage == "COLLSCAN"),
+            docs_examined: stat("totalDocsExamined"),
+            keys_examined: stat("totalKeysExamined"),
+            docs_returned: stat("nReturned"),
+            winning_plan,
+            indexes_used,
+            reply,
+        }
+    }
+}
+
+/// Collects the stage and index names of a plan and all of its input stages.
+fn walk_plan(plan: &Document, stages: &mut Vec<String>, indexes: &mut Vec<String>) {
+    if let Ok(stage) = plan.get_str("stage") {
+        stages.push(stage.to_string());
+    }
+    if let Ok(index) = plan.get_str("indexName") {
+        if !indexes.iter().any(|known| known == index) {
+            indexes.push(index.to_string());
+        }
+    }
+
+    for key in &["queryPlan", "inputStage"] {
+        if let Ok(input) = plan.get_document(key) {
+            walk_plan(input, stages, indexes);
+        }
+    }
+    if let Ok(inputs) = plan.get_array("inputStages") {
+        for input in inputs {
+            if let Bson::Document(input) = input {
+                walk_plan(input, stages, indexes);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExplainResult;
+
+    #[test]
+    fn test_explain_result_index_scan() {
+        let reply = doc! {
+            "queryPlanner": {
+                "winningPlan": {
+                    "stage": "FETCH",
+                    "inputStage": { "stage": "IXSCAN", "indexName": "name_1" },
+                },
+            },
+            "executionStats": {
+                "nReturned": 1,
+                "totalKeysExamined": 1,
+                "totalDocsExamined": 1,
+            },
+        };
+
+        let result = ExplainResult::from_reply(reply);
+        assert_eq!(vec!["name_1".to_string()], result.indexes_used);
+        assert!(!result.collection_scan);
+        assert_eq!(Some(1), result.docs_examined);
+        assert_eq!(Some(1), result.docs_returned);
+    }
+
+    #[test]
+    fn test_explain_result_aggregate_collection_scan() {
+        let reply = doc! {
+            "stages": [
+                { "$cursor": { "queryPlanner": { "winningPlan": { "stage": "COLLSCAN" } } } },
+                { "$group": { "_id": null } },
+            ],
+        };
+
+        let result = ExplainResult::from_reply(reply);
+        assert!(result.collection_scan);
+        assert!(result.indexes_used.is_empty());
+        assert_eq!(None, result.docs_examined);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{bindings, bsonc::Bsonc};
+use std::ptr;
+
+pub struct FindAndModifyOptsc {
+    inner: *mut bindings::mongoc_find_and_modify_opts_t,
+}
+
+impl FindAndModifyOptsc {
+    pub fn set_sort(&self, sort: &Bsonc) -> bool {
+        unsafe { bindings::mongoc_find_and_modify_opts_set_sort(self.inner, sort.as_ptr()) }
+    }
+
+    pub fn set_update(&self, update: &Bsonc) -> bool {
+        unsafe { bindings::mongoc_find_and_modify_opts_set_update(self.inner, update.as_ptr()) }
+    }
+
+    pub fn set_fields(&self, fields: &Bsonc) -> bool {
+        unsafe { bindings::mongoc_find_and_modify_opts_set_fields(self.inner, fields.as_ptr()) }
+    }
+
+    pub fn set_flags(&self, flags: bindings::mongoc_find_and_modify_flags_t) -> bool {
+        unsafe { bindings::mongoc_find_and_modify_opts_set_flags(self.inner, flags) }
+    }
+
+    pub fn set_bypass_document_validation(&self, bypass: bool) -> bool {
+        unsafe {
+            bindings::mongoc_find_and_modify_opts_set_bypass_document_validation(self.inner, bypass)
+        }
+    }
+
+    pub fn set_max_time_ms(&self, max_time_ms: u32) -> bool {
+        unsafe { bindings::mongoc_find_and_modify_opts_set_max_time_ms(self.inner, max_time_ms) }
+    }
+
+    /// Appends extra options such as writeConcern to the command.
+    pub fn append(&self, extra: &Bsonc) -> bool {
+        unsafe { bindings::mongoc_find_and_modify_opts_append(self.inner, extra.as_ptr()) }
+    }
+
+    pub fn as_ptr(&self) -> *const bindings::mongoc_find_and_modify_opts_t {
+        self.inner
+    }
+}
+
+impl Default for FindAndModifyOptsc {
+    fn default() -> Self {
+        let inner = unsafe { bindings::mongoc_find_and_modify_opts_new() };
+        assert!(!inner.is_null());
+
+        FindAndModifyOptsc { inner }
+    }
+}
+
+impl Drop for FindAndModifyOptsc {
+    fn drop(&mut self) {
+        if !self.inner.is_null() {
+            unsafe {
+                bindings::mongoc_find_and_modify_opts_destroy(self.inner);
+                self.inner = ptr::null_mut();
+            }
+        }
+    }
+}
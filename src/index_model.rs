@@ -0,0 +1,168 @@
+//! Description of an index on a collection.
+
+use bson::{Bson, Document};
+
+/// Keys and options of an index, used to create indexes and returned when listing them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexModel {
+    /// Fields the index is built on and their direction or type
+    pub keys: Document,
+    /// Name of the index, generated from the keys when not given
+    pub name: Option<String>,
+    /// Reject documents that duplicate an indexed value
+    pub unique: bool,
+    /// Only index documents that contain the indexed fields
+    pub sparse: bool,
+    /// TTL, remove documents this many seconds after the indexed date
+    pub expire_after_seconds: Option<i32>,
+    /// Only index documents matching this filter
+    pub partial_filter_expression: Option<Document>,
+    /// Collation used for string comparisons
+    pub collation: Option<Document>,
+    /// Build the index in the background, ignored since MongoDB 4.2
+    pub background: bool,
+    /// Hide the index from the query planner
+    pub hidden: bool,
+}
+
+impl Default for IndexModel {
+    fn default() -> Self {
+        IndexModel {
+            keys: Document::new(),
+            name: None,
+            unique: false,
+            sparse: false,
+            expire_after_seconds: None,
+            partial_filter_expression: None,
+            collation: None,
+            background: false,
+            hidden: false,
+        }
+    }
+}
+
+impl IndexModel {
+    /// Index on the given keys with default options.
+    pub fn new(keys: Document) -> Self {
+        IndexModel {
+            keys,
+            ..Default::default()
+        }
+    }
+
+    /// The index name, either given or as the server would generate it, e.g. `name_1_age_-1`.
+    pub fn name(&self) -> String {
+        if let Some(ref name) = self.name {
+            return name.clone();
+        }
+
+        self.keys
+            .iter()
+            .map(|(key, value)| match value {
+                Bson::String(value) => format!("{}_{}", key, value),
+                value => format!("{}_{}", key, value),
+            })
+            .collect::<Vec<String>>()
+            .join("_")
+    }
+
+    /// Index specification as expected by the createIndexes command.
+    pub(crate) fn to_document(&self) -> Document {
+        let mut d = doc! {
+            "key": self.keys.clone(),
+            "name": self.name(),
+        };
+
+        if self.unique {
+            d.insert("unique", true);
+        }
+        if self.sparse {
+            d.insert("sparse", true);
+        }
+        if let Some(seconds) = self.expire_after_seconds {
+            d.insert("expireAfterSeconds", seconds);
+        }
+        if let Some(ref filter) = self.partial_filter_expression {
+            d.insert("partialFilterExpression", filter.clone());
+        }
+        if let Some(ref collation) = self.collation {
+            d.insert("collation", collation.clone());
+        }
+        if self.background {
+            d.insert("background", true);
+        }
+        if self.hidden {
+            d.insert("hidden", true);
+        }
+
+        d
+    }
+
+    /// Reads an index specification as returned by listIndexes.
+    pub(crate) fn from_document(d: &Document) -> Self {
+        IndexModel {
+            keys: d.get_document("key").cloned().unwrap_or_default(),
+            name: d.get_str("name").ok().map(str::to_string),
+            unique: d.get_bool("unique").unwrap_or(false),
+            sparse: d.get_bool("sparse").unwrap_or(false),
+            expire_after_seconds: match d.get("expireAfterSeconds") {
+                Some(Bson::I32(seconds)) => Some(*seconds),
+                Some(Bson::I64(seconds)) => Some(*seconds as i32),
+                Some(Bson::FloatingPoint(seconds)) => Some(*seconds as i32),
+                _ => None,
+            },
+            partial_filter_expression: d.get_document("partialFilterExpression").ok().cloned(),
+            collation: d.get_document("collation").ok().cloned(),
+            background: d.get_bool("background").unwrap_or(false),
+            hidden: d.get_bool("hidden").unwrap_or(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IndexModel;
+
+    #[test]
+    fn test_index_model_name() {
+        let model = IndexModel::new(doc! { "name": 1, "age": -1 });
+        assert_eq!("name_1_age_-1", model.name());
+
+        let model = IndexModel::new(doc! { "body": "text" });
+        assert_eq!("body_text", model.name());
+
+        let model = IndexModel {
+            name: Some("custom".to_string()),
+            ..IndexModel::new(doc! { "name": 1 })
+        };
+        assert_eq!("custom", model.name());
+    }
+
+    #[test]
+    fn test_index_model_round_trip() {
+        let model = IndexModel {
+            unique: true,
+            expire_after_seconds: Some(3600),
+            partial_filter_expression: Some(doc! { "active": true }),
+            ..IndexModel::new(doc! { "created_at": 1 })
+        };
+
+        let spec = model.to_document();
+        assert_eq!(
+            doc! {
+                "key": { "created_at": 1 },
+                "name": "created_at_1",
+                "unique": true,
+                "expireAfterSeconds": 3600,
+                "partialFilterExpression": { "active": true },
+            },
+            spec
+        );
+
+        let parsed = IndexModel::from_document(&spec);
+        assert_eq!(Some("created_at_1".to_string()), parsed.name);
+        assert_eq!(model.keys, parsed.keys);
+        assert!(parsed.unique);
+        assert_eq!(Some(3600), parsed.expire_after_seconds);
+    }
+}
@@ -16,26 +16,42 @@ use std::sync::Once;
 
 use mongo_c_sys::bindings as bindings;
 
+#[cfg(any(feature = "tokio-runtime", feature = "async-std-runtime"))]
+mod async_client;
 mod bsonc;
 pub mod builder;
+mod bulk_write;
+mod change_event;
 mod change_stream;
+mod checkpoint;
 mod client;
 mod client_pool;
 mod collection;
+mod collection_spec;
 mod cursor;
 mod database;
+mod database_spec;
 mod error;
+mod explain;
+mod find_and_modify_opts;
 mod flags;
 mod host;
+mod index_model;
 mod options;
+mod pipeline;
+mod pooled;
 pub mod prelude;
 mod read_concern;
 mod read_prefs;
+mod results;
 mod session;
+mod session_collection;
 mod session_opts;
 mod ssl_options;
+mod tail_cursor;
 mod transaction_opts;
 mod uri;
+mod worker;
 mod write_concern;
 //mod write_opts;
 
@@ -1,11 +1,15 @@
 use crate::{
+    bindings,
     bsonc::Bsonc,
-    error::Result,
-    flags::{Flags, FlagsValue, QueryFlag},
-    read_prefs::ReadMode,
-    write_concern::WriteConcernLevel,
+    error::{InvalidParamsError, Result},
+    find_and_modify_opts::FindAndModifyOptsc,
+    flags::{Flags, QueryFlag},
+    read_concern::ReadConcernc,
+    read_prefs::ReadPrefsc,
+    session::Sessionc,
+    write_concern::WriteConcernc,
 };
-use bson::Document;
+use bson::{Bson, Document};
 use std::time::Duration;
 
 ///  to configure an aggregate operation.
@@ -13,6 +17,23 @@ pub struct Aggregate {
     /// Flags to use
     pub query_flags: Flags<QueryFlag>,
     pub batch_size: Option<i32>,
+    /// Allow stages to write temporary files when they exceed the memory limit
+    pub allow_disk_use: bool,
+    /// Time limit for the operation in milliseconds
+    pub max_time_ms: Option<i64>,
+    pub collation: Option<Document>,
+    /// Index to use
+    pub hint: Option<Hint>,
+    /// Comment attached to the operation in the server logs and profiler
+    pub comment: Option<String>,
+    /// Variables available in the pipeline as `$$name`, the `let` option
+    pub variables: Option<Document>,
+    /// Skip document validation in `$out` and `$merge` stages
+    pub bypass_document_validation: bool,
+    /// Read preference, the collection's is used if none is given
+    pub read_prefs: Option<ReadPrefsc>,
+    /// Read concern, the collection's is used if none is given
+    pub read_concern: Option<ReadConcernc>,
 }
 
 impl Default for Aggregate {
@@ -22,61 +43,111 @@ impl Default for Aggregate {
         Aggregate {
             query_flags: Flags::new(),
             batch_size: None,
+            allow_disk_use: false,
+            max_time_ms: None,
+            collation: None,
+            hint: None,
+            comment: None,
+            variables: None,
+            bypass_document_validation: false,
+            read_prefs: None,
+            read_concern: None,
         }
     }
 }
 
 impl Aggregate {
-    pub(crate) fn into_mongoc(self) -> Result<Bsonc> {
-        if let Some(size) = self.batch_size {
-            let d = doc! {
-                "batchSize": size,
-            };
-            Bsonc::from_document(&d)
-        } else {
-            Ok(Bsonc::empty())
-        }
+    pub(crate) fn into_mongoc(self) -> Result<(Bsonc, Option<ReadPrefsc>)> {
+        let opts = Bsonc::from_document(&self.to_document())?;
+
+        Ok((
+            append_read_concern(opts, self.read_concern)?,
+            self.read_prefs,
+        ))
     }
 
-    pub(crate) fn to_mongoc(&self) -> Result<Bsonc> {
+    fn to_document(&self) -> Document {
+        let mut d = Document::new();
+
         if let Some(size) = self.batch_size {
-            let d = doc! {
-                "batchSize": size,
-            };
-            Bsonc::from_document(&d)
-        } else {
-            Ok(Bsonc::empty())
+            d.insert("batchSize", size);
+        }
+        if self.allow_disk_use {
+            d.insert("allowDiskUse", true);
+        }
+        if let Some(max_time_ms) = self.max_time_ms {
+            d.insert("maxTimeMS", max_time_ms);
+        }
+        if let Some(ref collation) = self.collation {
+            d.insert("collation", collation.clone());
+        }
+        if let Some(ref hint) = self.hint {
+            d.insert("hint", hint.to_bson());
         }
+        if let Some(ref comment) = self.comment {
+            d.insert("comment", comment.clone());
+        }
+        if let Some(ref variables) = self.variables {
+            d.insert("let", variables.clone());
+        }
+        if self.bypass_document_validation {
+            d.insert("bypassDocumentValidation", true);
+        }
+
+        d
     }
 }
 
 ///  to configure a bulk operation.
 pub struct BulkOperation {
-    /// If the operations must be performed in order
+    /// If the operations must be performed in order, stopping at the first error
     pub ordered: bool,
-    /// `WriteConcern` to use
-    pub write_concern: WriteConcernLevel,
+    /// Write concern, the collection's is used if none is given
+    pub write_concern: Option<WriteConcernc>,
 }
 
 impl Default for BulkOperation {
     /// Default options that are used if no options are specified
-    /// when creating a `BulkOperation`.
+    /// when creating a `BulkOperation`, the same as mongoc's.
     fn default() -> Self {
         BulkOperation {
-            ordered: false,
-            write_concern: WriteConcernLevel::Blocking,
+            ordered: true,
+            write_concern: None,
         }
     }
 }
 
+impl BulkOperation {
+    pub(crate) fn into_mongoc(self) -> Result<Bsonc> {
+        let opts = Bsonc::from_document(&doc! { "ordered": self.ordered })?;
+
+        append_write_concern(opts, self.write_concern)
+    }
+}
+
+/// Which version of the document a find and modify operation returns.
+pub enum ReturnDocument {
+    /// The document as it was before it was modified
+    Before,
+    /// The document after the modification was applied
+    After,
+}
+
 ///  to configure a find and modify operation.
 pub struct FindAndModify {
     /// Sort order for the query
     pub sort: Option<Document>,
-    /// If the new version of the document should be returned
-    pub new: bool,
+    /// Which version of the document should be returned
+    pub return_document: ReturnDocument,
     /// The fields to return
     pub fields: Option<Document>,
+    /// Insert a document if none match the query
+    pub upsert: bool,
+    pub bypass_document_validation: bool,
+    /// Time limit for the operation on the server
+    pub max_time_ms: Option<u32>,
+    /// `WriteConcern` to use, None to inherit it from the collection
+    pub write_concern: Option<WriteConcernc>,
 }
 
 impl Default for FindAndModify {
@@ -84,17 +155,223 @@ impl Default for FindAndModify {
     fn default() -> Self {
         FindAndModify {
             sort: None,
-            new: false,
+            return_document: ReturnDocument::Before,
             fields: None,
+            upsert: false,
+            bypass_document_validation: false,
+            max_time_ms: None,
+            write_concern: None,
         }
     }
 }
 
 impl FindAndModify {
-    pub fn fields_bsonc(&self) -> Option<Bsonc> {
-        self.fields
-            .as_ref()
-            .map(|f| Bsonc::from_document(&f).expect("FindAndModify should be valid"))
+    pub(crate) fn into_mongoc(
+        self,
+        update: Option<&Document>,
+        remove: bool,
+    ) -> Result<FindAndModifyOptsc> {
+        let opts = FindAndModifyOptsc::default();
+        let mut valid = true;
+
+        if let Some(ref sort) = self.sort {
+            valid &= opts.set_sort(&Bsonc::from_document(sort)?);
+        }
+
+        if let Some(ref fields) = self.fields {
+            valid &= opts.set_fields(&Bsonc::from_document(fields)?);
+        }
+
+        if let Some(update) = update {
+            valid &= opts.set_update(&Bsonc::from_document(update)?);
+        }
+
+        let mut flags = bindings::mongoc_find_and_modify_flags_t_MONGOC_FIND_AND_MODIFY_NONE;
+        if remove {
+            flags |= bindings::mongoc_find_and_modify_flags_t_MONGOC_FIND_AND_MODIFY_REMOVE;
+        }
+        if self.upsert {
+            flags |= bindings::mongoc_find_and_modify_flags_t_MONGOC_FIND_AND_MODIFY_UPSERT;
+        }
+        if let ReturnDocument::After = self.return_document {
+            flags |= bindings::mongoc_find_and_modify_flags_t_MONGOC_FIND_AND_MODIFY_RETURN_NEW;
+        }
+        valid &= opts.set_flags(flags);
+
+        if self.bypass_document_validation {
+            valid &= opts.set_bypass_document_validation(true);
+        }
+
+        if let Some(max_time_ms) = self.max_time_ms {
+            valid &= opts.set_max_time_ms(max_time_ms);
+        }
+
+        if let Some(write_concern) = self.write_concern {
+            let mut extra = Bsonc::empty();
+            valid &= write_concern.append(&mut extra);
+            valid &= opts.append(&extra);
+        }
+
+        if valid {
+            Ok(opts)
+        } else {
+            Err(InvalidParamsError.into())
+        }
+    }
+}
+
+/// Index to use for an operation, either by name or by its key pattern.
+pub enum Hint {
+    Name(String),
+    Keys(Document),
+}
+
+impl Hint {
+    pub(crate) fn to_bson(&self) -> Bson {
+        match *self {
+            Hint::Name(ref name) => Bson::String(name.clone()),
+            Hint::Keys(ref keys) => Bson::Document(keys.clone()),
+        }
+    }
+}
+
+///  to configure a find operation.
+pub struct FindOptions {
+    /// Sort order for the results
+    pub sort: Option<Document>,
+    /// The fields to return
+    pub projection: Option<Document>,
+    /// Number of results to skip
+    pub skip: Option<i64>,
+    /// Limit to the number of results, a negative limit returns a single batch
+    pub limit: Option<i64>,
+    /// Number of documents to return per batch
+    pub batch_size: Option<i32>,
+    /// Index the query should use
+    pub hint: Option<Hint>,
+    /// Time limit for the query on the server
+    pub max_time_ms: Option<i64>,
+    pub collation: Option<Document>,
+    /// Comment to attach to the query to help trace it in the profiler and logs
+    pub comment: Option<String>,
+    /// Allow the server to write temporary data to disk for large sorts
+    pub allow_disk_use: bool,
+    /// Prevent the server from timing out idle cursors
+    pub no_cursor_timeout: bool,
+    /// Keep the cursor open after the last result, only for capped collections
+    pub tailable: bool,
+    /// Block on a tailable cursor waiting for new data instead of returning no results
+    pub await_data: bool,
+    /// Inclusive lower bound for the index given in `hint`
+    pub min: Option<Document>,
+    /// Exclusive upper bound for the index given in `hint`
+    pub max: Option<Document>,
+    /// Read preference, the collection's is used if none is given
+    pub read_prefs: Option<ReadPrefsc>,
+    /// Read concern, the collection's is used if none is given
+    pub read_concern: Option<ReadConcernc>,
+    /// Only return the index keys of the results
+    pub return_key: bool,
+}
+
+impl Default for FindOptions {
+    /// Default options used if none are provided.
+    fn default() -> Self {
+        FindOptions {
+            sort: None,
+            projection: None,
+            skip: None,
+            limit: None,
+            batch_size: None,
+            hint: None,
+            max_time_ms: None,
+            collation: None,
+            comment: None,
+            allow_disk_use: false,
+            no_cursor_timeout: false,
+            tailable: false,
+            await_data: false,
+            min: None,
+            max: None,
+            return_key: false,
+            read_prefs: None,
+            read_concern: None,
+        }
+    }
+}
+
+impl FindOptions {
+    pub(crate) fn into_mongoc(self) -> Result<(Bsonc, Option<ReadPrefsc>)> {
+        let opts = Bsonc::from_document(&self.to_document())?;
+
+        Ok((
+            append_read_concern(opts, self.read_concern)?,
+            self.read_prefs,
+        ))
+    }
+
+    /// Splits the options of an explained find, the fields go into the find command while
+    /// the read concern and read prefs apply to the explain command running it.
+    pub(crate) fn into_explain(self) -> Result<(Document, Bsonc, Option<ReadPrefsc>)> {
+        let fields = self.to_document();
+        let opts = append_read_concern(Bsonc::empty(), self.read_concern)?;
+
+        Ok((fields, opts, self.read_prefs))
+    }
+
+    pub(crate) fn to_document(&self) -> Document {
+        let mut d = Document::new();
+
+        if let Some(ref sort) = self.sort {
+            d.insert("sort", sort.clone());
+        }
+        if let Some(ref projection) = self.projection {
+            d.insert("projection", projection.clone());
+        }
+        if let Some(skip) = self.skip {
+            d.insert("skip", skip);
+        }
+        if let Some(limit) = self.limit {
+            d.insert("limit", limit);
+        }
+        if let Some(batch_size) = self.batch_size {
+            d.insert("batchSize", batch_size);
+        }
+        if let Some(ref hint) = self.hint {
+            d.insert("hint", hint.to_bson());
+        }
+        if let Some(max_time_ms) = self.max_time_ms {
+            d.insert("maxTimeMS", max_time_ms);
+        }
+        if let Some(ref collation) = self.collation {
+            d.insert("collation", collation.clone());
+        }
+        if let Some(ref comment) = self.comment {
+            d.insert("comment", comment.clone());
+        }
+        if self.allow_disk_use {
+            d.insert("allowDiskUse", true);
+        }
+        if self.no_cursor_timeout {
+            d.insert("noCursorTimeout", true);
+        }
+        if self.tailable {
+            d.insert("tailable", true);
+        }
+        if self.await_data {
+            d.insert("awaitData", true);
+        }
+        if let Some(ref min) = self.min {
+            d.insert("min", min.clone());
+        }
+        if let Some(ref max) = self.max {
+            d.insert("max", max.clone());
+        }
+        if self.return_key {
+            d.insert("returnKey", true);
+        }
+
+        d
     }
 }
 
@@ -104,23 +381,188 @@ pub struct Count {
     pub skip: u32,
     /// Limit to the number of results, zero to ignore
     pub limit: u32,
+    /// Read preference, the collection's is used if none is given
+    pub read_prefs: Option<ReadPrefsc>,
+    /// Read concern, the collection's is used if none is given
+    pub read_concern: Option<ReadConcernc>,
 }
 
 impl Count {
-    pub(crate) fn into_mongoc(self) -> Result<Bsonc> {
+    pub(crate) fn into_mongoc(self) -> Result<(Bsonc, Option<ReadPrefsc>)> {
         let d = doc! {
             "skip": self.skip,
             "limit": self.limit,
         };
+        let opts = Bsonc::from_document(&d)?;
 
-        Bsonc::from_document(&d)
+        Ok((
+            append_read_concern(opts, self.read_concern)?,
+            self.read_prefs,
+        ))
     }
 }
 
 impl Default for Count {
     /// Default options used if none are provided.
     fn default() -> Self {
-        Count { skip: 0, limit: 0 }
+        Count {
+            skip: 0,
+            limit: 0,
+            read_prefs: None,
+            read_concern: None,
+        }
+    }
+}
+
+///  to configure a count documents operation.
+pub struct CountDocuments {
+    /// Number of matching documents to skip
+    pub skip: Option<i64>,
+    /// Maximum number of documents to count
+    pub limit: Option<i64>,
+    /// Index to use
+    pub hint: Option<Hint>,
+    /// Time limit for the operation in milliseconds
+    pub max_time_ms: Option<i64>,
+    pub collation: Option<Document>,
+    /// Read preference, the collection's is used if none is given
+    pub read_prefs: Option<ReadPrefsc>,
+    /// Read concern, the collection's is used if none is given
+    pub read_concern: Option<ReadConcernc>,
+}
+
+impl Default for CountDocuments {
+    /// Default options used if none are provided.
+    fn default() -> Self {
+        CountDocuments {
+            skip: None,
+            limit: None,
+            hint: None,
+            max_time_ms: None,
+            collation: None,
+            read_prefs: None,
+            read_concern: None,
+        }
+    }
+}
+
+impl CountDocuments {
+    pub(crate) fn into_mongoc(self) -> Result<(Bsonc, Option<ReadPrefsc>)> {
+        let mut d = Document::new();
+
+        if let Some(skip) = self.skip {
+            d.insert("skip", skip);
+        }
+        if let Some(limit) = self.limit {
+            d.insert("limit", limit);
+        }
+        append_read_opts(&mut d, self.hint, self.max_time_ms, self.collation);
+
+        let opts = append_read_concern(Bsonc::from_document(&d)?, self.read_concern)?;
+
+        Ok((opts, self.read_prefs))
+    }
+}
+
+///  to configure an estimated document count operation.
+pub struct EstimatedDocumentCount {
+    /// Index to use
+    pub hint: Option<Hint>,
+    /// Time limit for the operation in milliseconds
+    pub max_time_ms: Option<i64>,
+    pub collation: Option<Document>,
+    /// Read preference, the collection's is used if none is given
+    pub read_prefs: Option<ReadPrefsc>,
+    /// Read concern, the collection's is used if none is given
+    pub read_concern: Option<ReadConcernc>,
+}
+
+impl Default for EstimatedDocumentCount {
+    /// Default options used if none are provided.
+    fn default() -> Self {
+        EstimatedDocumentCount {
+            hint: None,
+            max_time_ms: None,
+            collation: None,
+            read_prefs: None,
+            read_concern: None,
+        }
+    }
+}
+
+impl EstimatedDocumentCount {
+    pub(crate) fn into_mongoc(self) -> Result<(Bsonc, Option<ReadPrefsc>)> {
+        let mut d = Document::new();
+        append_read_opts(&mut d, self.hint, self.max_time_ms, self.collation);
+
+        let opts = append_read_concern(Bsonc::from_document(&d)?, self.read_concern)?;
+
+        Ok((opts, self.read_prefs))
+    }
+}
+
+///  to configure a distinct operation.
+pub struct Distinct {
+    /// Time limit for the operation in milliseconds
+    pub max_time_ms: Option<i64>,
+    pub collation: Option<Document>,
+    /// Read preference, the collection's is used if none is given
+    pub read_prefs: Option<ReadPrefsc>,
+    /// Read concern, the collection's is used if none is given
+    pub read_concern: Option<ReadConcernc>,
+}
+
+impl Default for Distinct {
+    /// Default options used if none are provided.
+    fn default() -> Self {
+        Distinct {
+            max_time_ms: None,
+            collation: None,
+            read_prefs: None,
+            read_concern: None,
+        }
+    }
+}
+
+impl Distinct {
+    pub(crate) fn into_mongoc(self) -> Result<(Bsonc, Option<ReadPrefsc>)> {
+        let mut d = Document::new();
+        append_read_opts(&mut d, None, self.max_time_ms, self.collation);
+
+        let opts = append_read_concern(Bsonc::from_document(&d)?, self.read_concern)?;
+
+        Ok((opts, self.read_prefs))
+    }
+}
+
+fn append_read_opts(
+    d: &mut Document,
+    hint: Option<Hint>,
+    max_time_ms: Option<i64>,
+    collation: Option<Document>,
+) {
+    if let Some(hint) = hint {
+        d.insert("hint", hint.to_bson());
+    }
+    if let Some(max_time_ms) = max_time_ms {
+        d.insert("maxTimeMS", max_time_ms);
+    }
+    if let Some(collation) = collation {
+        d.insert("collation", collation);
+    }
+}
+
+fn append_read_concern(mut opts: Bsonc, read_concern: Option<ReadConcernc>) -> Result<Bsonc> {
+    match read_concern {
+        Some(read_concern) if !read_concern.append(&mut opts) => Err(InvalidParamsError.into()),
+        _ => Ok(opts),
+    }
+}
+
+fn append_write_concern(mut opts: Bsonc, write_concern: Option<WriteConcernc>) -> Result<Bsonc> {
+    match write_concern {
+        Some(write_concern) if !write_concern.append(&mut opts) => Err(InvalidParamsError.into()),
+        _ => Ok(opts),
     }
 }
 
@@ -128,6 +570,8 @@ impl Default for Count {
 pub struct Insert {
     pub ordered: bool,
     pub bypass_document_validation: bool,
+    /// Write concern, the collection's is used if none is given
+    pub write_concern: Option<WriteConcernc>,
 }
 
 impl Default for Insert {
@@ -136,6 +580,7 @@ impl Default for Insert {
         Insert {
             ordered: true,
             bypass_document_validation: false,
+            write_concern: None,
         }
     }
 }
@@ -147,23 +592,28 @@ impl Insert {
             "bypassDocumentValidation": self.bypass_document_validation,
         };
 
-        Bsonc::from_document(&d)
+        append_write_concern(Bsonc::from_document(&d)?, self.write_concern)
     }
 }
 
-/// For future use when Transactions are working
-pub struct Remove;
+///  to configure a delete operation.
+pub struct Remove {
+    /// Write concern, the collection's is used if none is given
+    pub write_concern: Option<WriteConcernc>,
+}
 
 impl Default for Remove {
     /// Default options used if none are provided.
     fn default() -> Self {
-        Remove {}
+        Remove {
+            write_concern: None,
+        }
     }
 }
 
 impl Remove {
     pub(crate) fn into_mongoc(self) -> Result<Bsonc> {
-        Ok(Bsonc::empty())
+        append_write_concern(Bsonc::empty(), self.write_concern)
     }
 }
 
@@ -171,6 +621,8 @@ impl Remove {
 pub struct Update {
     pub upsert: bool,
     pub bypass_document_validation: bool,
+    /// Write concern, the collection's is used if none is given
+    pub write_concern: Option<WriteConcernc>,
 }
 
 impl Default for Update {
@@ -179,6 +631,7 @@ impl Default for Update {
         Update {
             upsert: false,
             bypass_document_validation: false,
+            write_concern: None,
         }
     }
 }
@@ -190,11 +643,269 @@ impl Update {
             "bypassDocumentValidation": self.bypass_document_validation,
         };
 
-        Bsonc::from_document(&d)
+        append_write_concern(Bsonc::from_document(&d)?, self.write_concern)
+    }
+}
+
+///  to configure an update_one operation.
+pub struct UpdateOne {
+    pub upsert: bool,
+    pub bypass_document_validation: bool,
+    /// Write concern, the collection's is used if none is given
+    pub write_concern: Option<WriteConcernc>,
+}
+
+impl Default for UpdateOne {
+    /// Default options used if none are provided.
+    fn default() -> Self {
+        UpdateOne {
+            upsert: false,
+            bypass_document_validation: false,
+            write_concern: None,
+        }
+    }
+}
+
+impl UpdateOne {
+    pub(crate) fn into_mongoc(self) -> Result<Bsonc> {
+        let d = doc! {
+            "upsert": self.upsert,
+            "bypassDocumentValidation": self.bypass_document_validation,
+        };
+
+        append_write_concern(Bsonc::from_document(&d)?, self.write_concern)
+    }
+}
+
+///  to configure a replace_one operation.
+pub struct ReplaceOne {
+    pub upsert: bool,
+    pub bypass_document_validation: bool,
+    /// Write concern, the collection's is used if none is given
+    pub write_concern: Option<WriteConcernc>,
+}
+
+impl Default for ReplaceOne {
+    /// Default options used if none are provided.
+    fn default() -> Self {
+        ReplaceOne {
+            upsert: false,
+            bypass_document_validation: false,
+            write_concern: None,
+        }
+    }
+}
+
+impl ReplaceOne {
+    pub(crate) fn into_mongoc(self) -> Result<Bsonc> {
+        let d = doc! {
+            "upsert": self.upsert,
+            "bypassDocumentValidation": self.bypass_document_validation,
+        };
+
+        append_write_concern(Bsonc::from_document(&d)?, self.write_concern)
+    }
+}
+
+/// Options of `Collection::delete_one_with_opts`, which deletes at most one of the
+/// documents matching the selector.
+pub struct RemoveOne {
+    /// Write concern, the collection's is used if none is given
+    pub write_concern: Option<WriteConcernc>,
+}
+
+impl Default for RemoveOne {
+    /// Default options used if none are provided.
+    fn default() -> Self {
+        RemoveOne {
+            write_concern: None,
+        }
+    }
+}
+
+impl RemoveOne {
+    pub(crate) fn into_mongoc(self) -> Result<Bsonc> {
+        append_write_concern(Bsonc::empty(), self.write_concern)
+    }
+}
+
+///  to configure the listing of databases.
+#[derive(Default)]
+pub struct ListDatabases {
+    /// Only list the databases the user has privileges on, the server decides if not given
+    pub authorized_databases: Option<bool>,
+    /// Only return the names, without sizes
+    pub name_only: bool,
+}
+
+impl ListDatabases {
+    pub(crate) fn to_command(&self, filter: Option<Document>) -> Document {
+        let mut command = doc! { "listDatabases": 1 };
+
+        if let Some(filter) = filter {
+            command.insert("filter", filter);
+        }
+        if self.name_only {
+            command.insert("nameOnly", true);
+        }
+        if let Some(authorized) = self.authorized_databases {
+            command.insert("authorizedDatabases", authorized);
+        }
+
+        command
+    }
+}
+
+///  to configure a database command.
+#[derive(Default)]
+pub struct RunCommand<'a> {
+    /// Session the command runs in
+    pub session: Option<&'a Sessionc>,
+    /// Read prefs, the primary is used if none are given.  Ignored by write commands.
+    pub read_prefs: Option<ReadPrefsc>,
+    /// Read concern, the database's is used by read commands if none is given
+    pub read_concern: Option<ReadConcernc>,
+    /// Write concern, the database's is used by write commands if none is given
+    pub write_concern: Option<WriteConcernc>,
+    /// Run the command on this server, see `mongoc_server_description_id`
+    pub server_id: Option<u32>,
+}
+
+impl RunCommand<'_> {
+    pub(crate) fn into_mongoc(self) -> Result<(Bsonc, Option<ReadPrefsc>)> {
+        let mut d = Document::new();
+        if let Some(server_id) = self.server_id {
+            d.insert("serverId", server_id as i32);
+        }
+
+        let opts = append_read_concern(Bsonc::from_document(&d)?, self.read_concern)?;
+        let mut opts = append_write_concern(opts, self.write_concern)?;
+        if let Some(session) = self.session {
+            session.append(&mut opts)?;
+        }
+
+        Ok((opts, self.read_prefs))
+    }
+}
+
+/// What change events of updates contain in `fullDocument`.
+pub enum FullDocument {
+    /// Only the changed fields, in `updateDescription`
+    Default,
+    /// The current version of the whole document, looked up when the event is read
+    UpdateLookup,
+    /// The document after the change, if the collection records post-images, requires
+    /// MongoDB 6.0 or later
+    WhenAvailable,
+    /// The document after the change, an error is raised when no post-image exists,
+    /// requires MongoDB 6.0 or later
+    Required,
+}
+
+/// Whether change events contain the document as it was before the change, requires
+/// MongoDB 6.0 or later and a collection recording pre-images.
+pub enum FullDocumentBeforeChange {
+    Off,
+    /// The pre-image, if the collection records pre-images
+    WhenAvailable,
+    /// The pre-image, an error is raised when none exists
+    Required,
+}
+
+/// A BSON timestamp, as found in `operationTime` and the `clusterTime` of change events.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Timestamp {
+    /// Seconds since the Unix epoch
+    pub time: u32,
+    /// Orders the operations within the same second
+    pub increment: u32,
+}
+
+impl Timestamp {
+    /// Reads a timestamp, bson keeps the time in the high and the increment in the low
+    /// 32 bits of `Bson::TimeStamp`.
+    pub(crate) fn from_bson(value: &Bson) -> Option<Self> {
+        match *value {
+            Bson::TimeStamp(packed) => Some(Timestamp {
+                time: (packed as u64 >> 32) as u32,
+                increment: packed as u32,
+            }),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn to_bson(self) -> Bson {
+        Bson::TimeStamp((u64::from(self.time) << 32 | u64::from(self.increment)) as i64)
+    }
+}
+
+///  to configure a change stream.
+#[derive(Default)]
+pub struct ChangeStreamOptions {
+    pub full_document: Option<FullDocument>,
+    pub full_document_before_change: Option<FullDocumentBeforeChange>,
+    /// Start after the event with this resume token
+    pub resume_after: Option<Document>,
+    /// Like `resume_after`, but also after an invalidate event
+    pub start_after: Option<Document>,
+    /// Start at this cluster time, e.g. the `cluster_time` of an event
+    pub start_at_operation_time: Option<Timestamp>,
+    /// Time the server waits for new changes before returning an empty batch
+    pub max_await_time_ms: Option<i64>,
+    pub batch_size: Option<i32>,
+    pub collation: Option<Document>,
+}
+
+impl ChangeStreamOptions {
+    pub(crate) fn into_mongoc(self) -> Result<Bsonc> {
+        Bsonc::from_document(&self.to_document())
+    }
+
+    fn to_document(&self) -> Document {
+        let mut d = Document::new();
+
+        if let Some(ref full_document) = self.full_document {
+            let full_document = match *full_document {
+                FullDocument::Default => "default",
+                FullDocument::UpdateLookup => "updateLookup",
+                FullDocument::WhenAvailable => "whenAvailable",
+                FullDocument::Required => "required",
+            };
+            d.insert("fullDocument", full_document);
+        }
+        if let Some(ref before_change) = self.full_document_before_change {
+            let before_change = match *before_change {
+                FullDocumentBeforeChange::Off => "off",
+                FullDocumentBeforeChange::WhenAvailable => "whenAvailable",
+                FullDocumentBeforeChange::Required => "required",
+            };
+            d.insert("fullDocumentBeforeChange", before_change);
+        }
+        if let Some(ref token) = self.resume_after {
+            d.insert("resumeAfter", token.clone());
+        }
+        if let Some(ref token) = self.start_after {
+            d.insert("startAfter", token.clone());
+        }
+        if let Some(timestamp) = self.start_at_operation_time {
+            d.insert("startAtOperationTime", timestamp.to_bson());
+        }
+        if let Some(max_await_time_ms) = self.max_await_time_ms {
+            d.insert("maxAwaitTimeMS", max_await_time_ms);
+        }
+        if let Some(batch_size) = self.batch_size {
+            d.insert("batchSize", batch_size);
+        }
+        if let Some(ref collation) = self.collation {
+            d.insert("collation", collation.clone());
+        }
+
+        d
     }
 }
 
 ///  to configure a tailing query.
+#[derive(Clone, Debug)]
 pub struct Tail {
     /// Duration to wait before checking for new results
     pub wait_duration: Duration,
@@ -211,3 +922,267 @@ impl Default for Tail {
         }
     }
 }
+
+/// What the server does with writes that fail the collection's validator.
+pub enum ValidationAction {
+    /// Reject the write
+    Error,
+    /// Accept the write and log a warning
+    Warn,
+}
+
+/// Which writes the collection's validator applies to.
+pub enum ValidationLevel {
+    /// No validation
+    Off,
+    /// Every insert and update
+    Strict,
+    /// Inserts and updates of documents that are already valid
+    Moderate,
+}
+
+/// Expected interval between measurements of a time series collection.
+pub enum TimeseriesGranularity {
+    Seconds,
+    Minutes,
+    Hours,
+}
+
+/// Layout of a time series collection.
+pub struct Timeseries {
+    /// Field holding the date of each measurement
+    pub time_field: String,
+    /// Field identifying the source of the measurements
+    pub meta_field: Option<String>,
+    pub granularity: Option<TimeseriesGranularity>,
+}
+
+/// Key a clustered collection stores its documents by, only `{"_id": 1}` is supported.
+pub struct ClusteredIndex {
+    pub key: Document,
+    pub unique: bool,
+    pub name: Option<String>,
+}
+
+impl Default for ClusteredIndex {
+    fn default() -> Self {
+        ClusteredIndex {
+            key: doc! { "_id": 1 },
+            unique: true,
+            name: None,
+        }
+    }
+}
+
+///  to configure the creation of a collection.
+#[derive(Default)]
+pub struct CreateCollectionOptions {
+    /// Create a fixed size collection, `size` must be given
+    pub capped: bool,
+    /// Maximum size in bytes of a capped collection
+    pub size: Option<i64>,
+    /// Maximum number of documents in a capped collection
+    pub max: Option<i64>,
+    /// Query documents must match to be inserted or updated
+    pub validator: Option<Document>,
+    pub validation_level: Option<ValidationLevel>,
+    pub validation_action: Option<ValidationAction>,
+    /// Default collation of the collection
+    pub collation: Option<Document>,
+    /// Storage engine specific options, e.g. `{"wiredTiger": {"configString": ".."}}`
+    pub storage_engine: Option<Document>,
+    /// Create a time series collection
+    pub timeseries: Option<Timeseries>,
+    /// Remove documents of a time series or clustered collection after this many seconds
+    pub expire_after_seconds: Option<i64>,
+    /// Create a clustered collection
+    pub clustered_index: Option<ClusteredIndex>,
+    /// Write concern, the database's is used if none is given
+    pub write_concern: Option<WriteConcernc>,
+}
+
+impl CreateCollectionOptions {
+    pub(crate) fn into_mongoc(self) -> Result<Bsonc> {
+        let opts = Bsonc::from_document(&self.to_document())?;
+
+        append_write_concern(opts, self.write_concern)
+    }
+
+    fn to_document(&self) -> Document {
+        let mut d = Document::new();
+
+        if self.capped {
+            d.insert("capped", true);
+        }
+        if let Some(size) = self.size {
+            d.insert("size", size);
+        }
+        if let Some(max) = self.max {
+            d.insert("max", max);
+        }
+        if let Some(ref validator) = self.validator {
+            d.insert("validator", validator.clone());
+        }
+        if let Some(ref level) = self.validation_level {
+            let level = match *level {
+                ValidationLevel::Off => "off",
+                ValidationLevel::Strict => "strict",
+                ValidationLevel::Moderate => "moderate",
+            };
+            d.insert("validationLevel", level);
+        }
+        if let Some(ref action) = self.validation_action {
+            let action = match *action {
+                ValidationAction::Error => "error",
+                ValidationAction::Warn => "warn",
+            };
+            d.insert("validationAction", action);
+        }
+        if let Some(ref collation) = self.collation {
+            d.insert("collation", collation.clone());
+        }
+        if let Some(ref storage_engine) = self.storage_engine {
+            d.insert("storageEngine", storage_engine.clone());
+        }
+        if let Some(ref timeseries) = self.timeseries {
+            let mut ts = doc! { "timeField": timeseries.time_field.clone() };
+            if let Some(ref meta_field) = timeseries.meta_field {
+                ts.insert("metaField", meta_field.clone());
+            }
+            if let Some(ref granularity) = timeseries.granularity {
+                let granularity = match *granularity {
+                    TimeseriesGranularity::Seconds => "seconds",
+                    TimeseriesGranularity::Minutes => "minutes",
+                    TimeseriesGranularity::Hours => "hours",
+                };
+                ts.insert("granularity", granularity);
+            }
+            d.insert("timeseries", ts);
+        }
+        if let Some(seconds) = self.expire_after_seconds {
+            d.insert("expireAfterSeconds", seconds);
+        }
+        if let Some(ref clustered_index) = self.clustered_index {
+            let mut index = doc! {
+                "key": clustered_index.key.clone(),
+                "unique": clustered_index.unique,
+            };
+            if let Some(ref name) = clustered_index.name {
+                index.insert("name", name.clone());
+            }
+            d.insert("clusteredIndex", index);
+        }
+
+        d
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Aggregate, ChangeStreamOptions, CreateCollectionOptions, FindOptions, FullDocument,
+        FullDocumentBeforeChange, Hint, Timeseries, TimeseriesGranularity, Timestamp,
+        ValidationAction,
+    };
+    use bson::Bson;
+
+    #[test]
+    fn test_find_options_to_document() {
+        let opts = FindOptions {
+            sort: Some(doc! {"name": 1}),
+            limit: Some(10),
+            hint: Some(Hint::Name("name_1".to_string())),
+            tailable: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            doc! {
+                "sort": {"name": 1},
+                "limit": 10i64,
+                "hint": "name_1",
+                "tailable": true,
+            },
+            opts.to_document()
+        );
+    }
+
+    #[test]
+    fn test_find_options_default_is_empty() {
+        assert!(FindOptions::default().to_document().is_empty());
+    }
+
+    #[test]
+    fn test_aggregate_to_document() {
+        let opts = Aggregate {
+            allow_disk_use: true,
+            max_time_ms: Some(500),
+            variables: Some(doc! {"min": 2}),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            doc! {
+                "allowDiskUse": true,
+                "maxTimeMS": 500i64,
+                "let": {"min": 2},
+            },
+            opts.to_document()
+        );
+        assert!(Aggregate::default().to_document().is_empty());
+    }
+
+    #[test]
+    fn test_create_collection_options_to_document() {
+        let opts = CreateCollectionOptions {
+            capped: true,
+            size: Some(4096),
+            validator: Some(doc! { "name": { "$exists": true } }),
+            validation_action: Some(ValidationAction::Warn),
+            timeseries: Some(Timeseries {
+                time_field: "ts".to_string(),
+                meta_field: None,
+                granularity: Some(TimeseriesGranularity::Minutes),
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            doc! {
+                "capped": true,
+                "size": 4096i64,
+                "validator": { "name": { "$exists": true } },
+                "validationAction": "warn",
+                "timeseries": { "timeField": "ts", "granularity": "minutes" },
+            },
+            opts.to_document()
+        );
+    }
+
+    #[test]
+    fn test_change_stream_options_to_document() {
+        let opts = ChangeStreamOptions {
+            full_document: Some(FullDocument::UpdateLookup),
+            full_document_before_change: Some(FullDocumentBeforeChange::WhenAvailable),
+            resume_after: Some(doc! { "_data": "8263" }),
+            start_at_operation_time: Some(Timestamp {
+                time: 1_600_000_000,
+                increment: 3,
+            }),
+            max_await_time_ms: Some(1000),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            doc! {
+                "fullDocument": "updateLookup",
+                "fullDocumentBeforeChange": "whenAvailable",
+                "resumeAfter": { "_data": "8263" },
+                "startAtOperationTime": Bson::TimeStamp(1_600_000_000 << 32 | 3),
+                "maxAwaitTimeMS": 1000i64,
+            },
+            opts.to_document()
+        );
+        assert!(ChangeStreamOptions::default().to_document().is_empty());
+    }
+}
@@ -0,0 +1,249 @@
+//! Builder for aggregation pipelines.
+
+use bson::{Bson, Document};
+
+/// Stages of an aggregation pipeline, in the order they run.
+///
+/// ```
+/// #[macro_use]
+/// extern crate bson;
+/// use mongo_leaf::prelude::*;
+///
+/// # fn main() {
+/// let pipeline = Pipeline::new()
+///     .filter(doc!{"status": "active"})
+///     .group(doc!{"_id": "$team", "total": {"$sum": "$points"}})
+///     .sort(doc!{"total": -1})
+///     .limit(3);
+///
+/// assert_eq!(4, pipeline.stages().len());
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Pipeline {
+    stages: Vec<Document>,
+}
+
+impl Pipeline {
+    /// An empty pipeline.
+    pub fn new() -> Self {
+        Pipeline { stages: Vec::new() }
+    }
+
+    /// Adds a `$match` stage.
+    pub fn filter(self, filter: Document) -> Self {
+        self.operator("$match", filter)
+    }
+
+    /// Adds a `$project` stage.
+    pub fn project(self, projection: Document) -> Self {
+        self.operator("$project", projection)
+    }
+
+    /// Adds a `$group` stage, the document must contain the `_id` to group by.
+    pub fn group(self, group: Document) -> Self {
+        self.operator("$group", group)
+    }
+
+    /// Adds a `$sort` stage.
+    pub fn sort(self, sort: Document) -> Self {
+        self.operator("$sort", sort)
+    }
+
+    /// Adds a `$limit` stage.
+    pub fn limit(self, limit: i64) -> Self {
+        self.operator("$limit", limit)
+    }
+
+    /// Adds a `$skip` stage.
+    pub fn skip(self, skip: i64) -> Self {
+        self.operator("$skip", skip)
+    }
+
+    /// Adds a `$lookup` stage joining documents of another collection on equal fields.
+    pub fn lookup(
+        self,
+        from: &str,
+        local_field: &str,
+        foreign_field: &str,
+        as_field: &str,
+    ) -> Self {
+        self.operator(
+            "$lookup",
+            doc! {
+                "from": from,
+                "localField": local_field,
+                "foreignField": foreign_field,
+                "as": as_field,
+            },
+        )
+    }
+
+    /// Adds a `$lookup` stage running a pipeline on another collection, `variables` are
+    /// available in the pipeline as `$$name`.
+    pub fn lookup_pipeline(
+        self,
+        from: &str,
+        variables: Document,
+        pipeline: Pipeline,
+        as_field: &str,
+    ) -> Self {
+        self.operator(
+            "$lookup",
+            doc! {
+                "from": from,
+                "let": variables,
+                "pipeline": pipeline.into_bson(),
+                "as": as_field,
+            },
+        )
+    }
+
+    /// Adds an `$unwind` stage for the array at `path`, e.g. `$tags`.
+    pub fn unwind(self, path: &str) -> Self {
+        self.operator("$unwind", path)
+    }
+
+    /// Adds an `$unwind` stage that keeps documents without the array and optionally
+    /// stores the array index in `index_field`.
+    pub fn unwind_with_opts(
+        self,
+        path: &str,
+        preserve_null_and_empty_arrays: bool,
+        index_field: Option<&str>,
+    ) -> Self {
+        let mut unwind = doc! {
+            "path": path,
+            "preserveNullAndEmptyArrays": preserve_null_and_empty_arrays,
+        };
+        if let Some(index_field) = index_field {
+            unwind.insert("includeArrayIndex", index_field);
+        }
+
+        self.operator("$unwind", unwind)
+    }
+
+    /// Adds a `$facet` stage running each named pipeline on the same input.
+    pub fn facet(self, facets: Vec<(&str, Pipeline)>) -> Self {
+        let mut facet = Document::new();
+        for (name, pipeline) in facets {
+            facet.insert(name, pipeline.into_bson());
+        }
+
+        self.operator("$facet", facet)
+    }
+
+    /// Adds an `$addFields` stage.
+    pub fn add_fields(self, fields: Document) -> Self {
+        self.operator("$addFields", fields)
+    }
+
+    /// Adds an `$out` stage writing the results to a collection, it must be the last stage.
+    pub fn out(self, collection: &str) -> Self {
+        self.operator("$out", collection)
+    }
+
+    /// Adds a `$merge` stage, `merge` holds `into` and optionally `on`, `whenMatched`
+    /// and `whenNotMatched`.  It must be the last stage.
+    pub fn merge(self, merge: Document) -> Self {
+        self.operator("$merge", merge)
+    }
+
+    /// Adds a stage with any operator, e.g. `("$sample", doc!{"size": 5})`.
+    pub fn operator(self, operator: &str, value: impl Into<Bson>) -> Self {
+        let mut stage = Document::new();
+        stage.insert(operator, value.into());
+
+        self.stage(stage)
+    }
+
+    /// Adds a complete stage document as is.
+    pub fn stage(mut self, stage: Document) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// The stages added so far.
+    pub fn stages(&self) -> &[Document] {
+        &self.stages
+    }
+
+    pub(crate) fn into_bson(self) -> Bson {
+        Bson::Array(self.stages.into_iter().map(Bson::Document).collect())
+    }
+
+    /// The pipeline as expected by `mongoc_collection_aggregate`.
+    pub(crate) fn into_document(self) -> Document {
+        doc! { "pipeline": self.into_bson() }
+    }
+}
+
+impl From<Vec<Document>> for Pipeline {
+    fn from(stages: Vec<Document>) -> Self {
+        Pipeline { stages }
+    }
+}
+
+/// Accepts the `{"pipeline": [...]}` form, any other document is used as a single stage.
+impl From<Document> for Pipeline {
+    fn from(mut pipeline: Document) -> Self {
+        match pipeline.remove("pipeline") {
+            Some(Bson::Array(stages)) if pipeline.is_empty() => Pipeline {
+                stages: stages
+                    .into_iter()
+                    .filter_map(|stage| match stage {
+                        Bson::Document(stage) => Some(stage),
+                        _ => None,
+                    })
+                    .collect(),
+            },
+            Some(value) => {
+                pipeline.insert("pipeline", value);
+                Pipeline::new().stage(pipeline)
+            }
+            None => Pipeline::new().stage(pipeline),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pipeline;
+
+    #[test]
+    fn test_pipeline_stages() {
+        let pipeline = Pipeline::new()
+            .filter(doc! { "num": { "$gt": 1 } })
+            .unwind_with_opts("$tags", true, Some("idx"))
+            .facet(vec![("count", Pipeline::new().operator("$count", "n"))])
+            .limit(5);
+
+        assert_eq!(
+            doc! {
+                "pipeline": [
+                    { "$match": { "num": { "$gt": 1 } } },
+                    { "$unwind": {
+                        "path": "$tags",
+                        "preserveNullAndEmptyArrays": true,
+                        "includeArrayIndex": "idx",
+                    } },
+                    { "$facet": { "count": [{ "$count": "n" }] } },
+                    { "$limit": 5i64 },
+                ]
+            },
+            pipeline.into_document()
+        );
+    }
+
+    #[test]
+    fn test_pipeline_from_document() {
+        let pipeline = Pipeline::from(doc! { "pipeline": [{ "$limit": 1 }] });
+        assert_eq!(vec![doc! { "$limit": 1 }], pipeline.stages());
+
+        let pipeline = Pipeline::from(doc! { "$match": { "name": "foo" } });
+        assert_eq!(
+            vec![doc! { "$match": { "name": "foo" } }],
+            pipeline.stages()
+        );
+    }
+}
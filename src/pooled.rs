@@ -0,0 +1,243 @@
+//! Clients, databases and collections that own a client of a shared pool, so they can be
+//! moved into threads and tasks outliving the scope of the pool.
+
+use crate::{
+    change_stream::PooledChangeStream,
+    client::{Client, Clientc},
+    collection::{Collection, Collectionc},
+    cursor::PooledCursor,
+    database::{Database, Databasec},
+    options::{Aggregate, ChangeStreamOptions, FindOptions, Tail},
+    pipeline::Pipeline,
+    tail_cursor::PooledTailCursor,
+};
+
+/// A client owning a reference counted handle to its pool instead of borrowing it, see
+/// `ClientPoolc::pop_pooled`.  The client goes back to the pool when dropped.
+///
+/// The client is only lent to `run`.  Databases, collections, cursors and streams created
+/// there must not be kept past the call, e.g. in a `thread_local!` or with `Box::leak`, as
+/// they would keep using the client after the `PooledClient` moved to another thread.
+///
+/// # Examples
+/// ```compile_fail
+/// use mongo_leaf::prelude::*;
+/// use std::{sync::Arc, thread};
+///
+/// # fn main() -> Result<()> {
+/// let builder = Builder::new();
+/// let pool = Arc::new(builder.connect()?);
+/// let client = pool.pop_pooled();
+///
+/// // A collection is not `Send`, `run` can't return it.
+/// let collection = client.run(|client| client.get_collection("db", "test"));
+/// thread::spawn(move || collection.count(None));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PooledClient {
+    client: Clientc<'static>,
+}
+
+// Nothing but `run` reaches the mongoc_client_t.  Its closure can't return or send away the
+// databases, collections and cursors it creates, none of them is `Send`, but nothing stops
+// it from stashing one in a `thread_local!` or leaking it and using it after the
+// `PooledClient` moved on.  The client is only used by one thread at a time as long as
+// callers don't, which the compiler does not check.
+unsafe impl Send for PooledClient {}
+
+impl PooledClient {
+    pub(crate) fn new(client: Clientc<'static>) -> Self {
+        PooledClient { client }
+    }
+
+    /// Runs blocking calls with the client on the current thread.
+    pub fn run<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&Clientc) -> R + Send,
+        R: Send,
+    {
+        f(&self.client)
+    }
+
+    /// The database, owning the client until it is dropped.
+    pub fn database(self, name: impl Into<String>) -> PooledDatabase {
+        PooledDatabase {
+            database: self.client.get_database(name),
+            client: self,
+        }
+    }
+
+    /// The database given in the connection URI, owning the client until it is dropped.
+    pub fn default_database(self) -> PooledDatabase {
+        PooledDatabase {
+            database: self.client.default_database(),
+            client: self,
+        }
+    }
+
+    /// The collection, owning the client until it is dropped.
+    pub fn collection(
+        self,
+        db_name: impl Into<String>,
+        collection_name: impl Into<String>,
+    ) -> PooledCollection {
+        PooledCollection {
+            collection: self.client.get_collection(db_name, collection_name),
+            _client: self,
+        }
+    }
+
+    /// Watches every database of the deployment on a worker thread, which owns the client
+    /// until the stream is dropped.
+    pub fn watch(
+        self,
+        pipeline: Option<bson::Document>,
+        opts: Option<ChangeStreamOptions>,
+    ) -> PooledChangeStream {
+        PooledChangeStream::open(move || {
+            let stream = self.client.watch(pipeline, opts)?;
+            Ok((stream, self))
+        })
+    }
+}
+
+/// A database together with the pooled client it was obtained from.
+#[derive(Debug)]
+pub struct PooledDatabase {
+    /// Declared first so it is destroyed before the client goes back to the pool
+    database: Databasec,
+    client: PooledClient,
+}
+
+// The database is the only user of the client it owns and is only lent to `run`, with the
+// same caveat as `PooledClient`: a collection or cursor created there and kept past the
+// call still uses the client.
+unsafe impl Send for PooledDatabase {}
+
+impl PooledDatabase {
+    /// Runs blocking calls with the database on the current thread.
+    pub fn run<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&Databasec) -> R + Send,
+        R: Send,
+    {
+        f(&self.database)
+    }
+
+    /// A collection of the database, taking over the client.
+    pub fn collection(self, name: impl Into<String>) -> PooledCollection {
+        let collection = self.database.get_collection(name);
+        let PooledDatabase { database, client } = self;
+        drop(database);
+
+        PooledCollection {
+            collection,
+            _client: client,
+        }
+    }
+
+    /// Watches the database on a worker thread, which owns the client until the stream is
+    /// dropped.
+    pub fn watch(
+        self,
+        pipeline: Option<bson::Document>,
+        opts: Option<ChangeStreamOptions>,
+    ) -> PooledChangeStream {
+        PooledChangeStream::open(move || {
+            let stream = self.database.watch(pipeline, opts)?;
+            Ok((stream, self))
+        })
+    }
+}
+
+/// A collection together with the pooled client it was obtained from.
+///
+/// # Examples
+/// ```
+/// #[macro_use]
+/// extern crate bson;
+/// use mongo_leaf::prelude::*;
+/// use std::{env, sync::Arc, thread};
+///
+/// # fn main() -> Result<()> {
+/// env::set_var("MONGODB_URI","mongodb://standard");
+/// let builder = Builder::new();
+/// let pool = Arc::new(builder.random_database_connect()?);
+/// let collection = pool.pop_pooled().default_database().collection("test");
+///
+/// let handle = thread::spawn(move || {
+///     collection.run(|collection| collection.insert_one(doc!{"name": "omg"}))
+/// });
+/// handle.join().unwrap()?;
+///
+/// # pool.pop().default_database().destroy();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PooledCollection {
+    /// Declared first so it is destroyed before the client goes back to the pool
+    collection: Collectionc,
+    /// Held until the collection is dropped
+    _client: PooledClient,
+}
+
+// The collection is the only user of the client it owns and is only lent to `run`, with
+// the same caveat as `PooledClient`: a cursor, stream or bulk write created there and kept
+// past the call still uses the client.
+unsafe impl Send for PooledCollection {}
+
+impl PooledCollection {
+    /// Runs blocking calls with the collection on the current thread.
+    pub fn run<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&Collectionc) -> R + Send,
+        R: Send,
+    {
+        f(&self.collection)
+    }
+
+    /// Finds the documents on a worker thread, which owns the client until the cursor is
+    /// dropped.
+    pub fn find(self, filter: bson::Document, opts: Option<FindOptions>) -> PooledCursor {
+        PooledCursor::open(move || {
+            let cursor = self.collection.find_with_opts(filter, opts);
+            Ok((cursor, self))
+        })
+    }
+
+    /// Follows the capped collection on a worker thread, which owns the client until the
+    /// cursor is dropped.  See `Collection::tail`.
+    pub fn tail(self, filter: bson::Document, opts: Tail) -> PooledTailCursor {
+        PooledTailCursor::open(move || {
+            let tail = self.collection.tail(filter, opts);
+            Ok((tail, self))
+        })
+    }
+
+    /// Runs the pipeline on a worker thread, which owns the client until the cursor is
+    /// dropped.
+    pub fn aggregate(self, pipeline: impl Into<Pipeline>, opts: Option<Aggregate>) -> PooledCursor {
+        let pipeline = pipeline.into();
+
+        PooledCursor::open(move || {
+            let cursor = self.collection.aggregate_with_opts(pipeline, opts);
+            Ok((cursor, self))
+        })
+    }
+
+    /// Watches the collection on a worker thread, which owns the client until the stream
+    /// is dropped.
+    pub fn watch(
+        self,
+        pipeline: Option<bson::Document>,
+        opts: Option<ChangeStreamOptions>,
+    ) -> PooledChangeStream {
+        PooledChangeStream::open(move || {
+            let stream = self.collection.watch(pipeline, opts)?;
+            Ok((stream, self))
+        })
+    }
+}
@@ -1,16 +1,43 @@
 pub use crate::{
     builder::{Builder, Connect, ConstructUri, SSLOptions},
+    bulk_write::{BulkWrite, WriteModel},
+    change_event::{ChangeEvent, ChangeEventInfo, Namespace, TruncatedArray, UpdateDescription},
+    change_stream::{ChangeStream, PooledChangeStream},
+    checkpoint::{
+        CheckpointStore, CollectionCheckpointStore, FileCheckpointStore, ResumableWatcher,
+    },
     client::Client,
-    client_pool::ClientPool,
+    client_pool::{ClientPool, PoolHandle, PoolStats},
     collection::Collection,
+    collection_spec::{CollectionSpec, CollectionType},
+    cursor::PooledCursor,
     database::Database,
+    database_spec::DatabaseSpec,
     error::{
         BsoncError, BulkOperationError, InvalidParamsError, MongoError, MongoErrorCode,
-        MongoErrorDomain, Result,
+        MongoErrorDomain, PoolExhaustedError, Result, WriteError,
     },
+    explain::{ExplainResult, ExplainVerbosity},
     host::Host,
-    options::Count,
-    read_prefs::{ReadMode, ReadPrefs},
+    index_model::IndexModel,
+    options::{
+        Aggregate, BulkOperation, ChangeStreamOptions, ClusteredIndex, Count, CountDocuments,
+        CreateCollectionOptions, Distinct, EstimatedDocumentCount, FindAndModify, FindOptions,
+        FullDocument, FullDocumentBeforeChange, Hint, Insert, ListDatabases, Remove, RemoveOne,
+        ReplaceOne, ReturnDocument, RunCommand, Tail, Timeseries, TimeseriesGranularity, Timestamp,
+        Update, UpdateOne, ValidationAction, ValidationLevel,
+    },
+    pipeline::Pipeline,
+    pooled::{PooledClient, PooledCollection, PooledDatabase},
+    read_concern::{ReadConcern, ReadConcernLevel, ReadConcernc},
+    read_prefs::{ReadMode, ReadPrefs, ReadPrefsc},
+    results::{BulkWriteResult, DeleteResult, InsertManyResult, InsertOneResult, UpdateResult},
     session::Session,
+    session_collection::{InSession, SessionCollection},
+    tail_cursor::{PooledTailCursor, TailCursor},
     uri::{Uri, Uric},
+    write_concern::{WriteConcern, WriteConcernLevel, WriteConcernc},
 };
+
+#[cfg(any(feature = "tokio-runtime", feature = "async-std-runtime"))]
+pub use crate::async_client::{AsyncClient, AsyncCollection, AsyncDatabase};
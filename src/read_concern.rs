@@ -63,6 +63,14 @@ pub struct ReadConcernc {
 }
 
 impl ReadConcernc {
+    /// Copies a read concern owned by the C driver.
+    pub(crate) fn copy_from_ptr(ptr: *const bindings::mongoc_read_concern_t) -> Self {
+        assert!(!ptr.is_null());
+        let inner = unsafe { bindings::mongoc_read_concern_copy(ptr) };
+        assert!(!inner.is_null());
+        ReadConcernc { inner }
+    }
+
     pub fn append(&self, opts: &mut Bsonc) -> bool {
         unsafe { bindings::mongoc_read_concern_append(self.inner, opts.as_mut_ptr()) }
     }
@@ -103,7 +111,7 @@ impl ReadConcern for ReadConcernc {
 
 impl ReadConcernc {
     /// Create a new read concern
-    fn new(level: ReadConcernLevel) -> Result<ReadConcernc> {
+    pub fn new(level: ReadConcernLevel) -> Result<ReadConcernc> {
         let inner = unsafe { bindings::mongoc_read_concern_new() };
         assert!(!inner.is_null());
 
@@ -118,6 +126,10 @@ impl ReadConcernc {
     }
 }
 
+// The mongoc_read_concern_t is made by `new` or copied from the driver's, and setters such
+// as `set_read_concern` copy it again, so the ReadConcernc is its only owner.
+unsafe impl Send for ReadConcernc {}
+
 impl Drop for ReadConcernc {
     fn drop(&mut self) {
         assert!(!self.inner.is_null());
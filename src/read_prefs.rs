@@ -33,6 +33,16 @@ pub struct ReadPrefsc {
     inner: *mut bindings::mongoc_read_prefs_t,
 }
 
+impl ReadPrefsc {
+    /// Copies read prefs owned by the C driver.
+    pub(crate) fn copy_from_ptr(ptr: *const bindings::mongoc_read_prefs_t) -> Self {
+        assert!(!ptr.is_null());
+        let inner = unsafe { bindings::mongoc_read_prefs_copy(ptr) };
+        assert!(!inner.is_null());
+        ReadPrefsc { inner }
+    }
+}
+
 impl Default for ReadPrefsc {
     /// Get a new instance of the default read pref.
     fn default() -> Self {
@@ -62,6 +72,10 @@ impl ReadPrefs for ReadPrefsc {
     }
 }
 
+// Each ReadPrefsc holds its own mongoc_read_prefs_t, new or copied in `from_ptr`, and the
+// driver keeps a copy of its own wherever read prefs are set, so nothing else points to it.
+unsafe impl Send for ReadPrefsc {}
+
 impl Drop for ReadPrefsc {
     fn drop(&mut self) {
         assert!(!self.inner.is_null());
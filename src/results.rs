@@ -0,0 +1,164 @@
+//! Typed results returned by the write operations on a `Collection`.
+
+use bson::{Bson, Document};
+use std::collections::HashMap;
+
+/// Result of inserting a single document.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InsertOneResult {
+    /// The `_id` of the inserted document, generated on the client when missing.
+    pub inserted_id: Bson,
+}
+
+/// Result of inserting many documents.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InsertManyResult {
+    /// The `_id` of every inserted document, in the order they were given.
+    pub inserted_ids: Vec<Bson>,
+}
+
+/// Result of an update operation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateResult {
+    /// Number of documents matching the selector
+    pub matched: i64,
+    /// Number of documents that were actually modified
+    pub modified: i64,
+    /// The `_id` of the inserted document if the update resulted in an upsert
+    pub upserted_id: Option<Bson>,
+}
+
+/// Result of a delete operation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeleteResult {
+    /// Number of documents deleted
+    pub deleted: i64,
+}
+
+/// Result of executing a bulk write.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BulkWriteResult {
+    /// Number of documents inserted
+    pub inserted: i64,
+    /// Number of documents matching the selectors of update and replace models
+    pub matched: i64,
+    /// Number of documents that were actually modified
+    pub modified: i64,
+    /// Number of documents deleted
+    pub deleted: i64,
+    /// Number of documents upserted
+    pub upserted: i64,
+    /// The `_id` of every inserted document, keyed by the index of its model
+    pub inserted_ids: HashMap<usize, Bson>,
+    /// The `_id` of every upserted document, keyed by the index of its model
+    pub upserted_ids: HashMap<usize, Bson>,
+}
+
+impl UpdateResult {
+    pub(crate) fn from_reply(reply: &Document) -> Self {
+        UpdateResult {
+            matched: reply_count(reply, "matchedCount"),
+            modified: reply_count(reply, "modifiedCount"),
+            upserted_id: reply.get("upsertedId").cloned(),
+        }
+    }
+}
+
+impl DeleteResult {
+    pub(crate) fn from_reply(reply: &Document) -> Self {
+        DeleteResult {
+            deleted: reply_count(reply, "deletedCount"),
+        }
+    }
+}
+
+impl BulkWriteResult {
+    pub(crate) fn from_reply(reply: &Document, inserted_ids: HashMap<usize, Bson>) -> Self {
+        let upserted_ids = match reply.get_array("upserted") {
+            Ok(upserted) => upserted
+                .iter()
+                .filter_map(|entry| match entry {
+                    Bson::Document(entry) => Some((
+                        reply_count(entry, "index") as usize,
+                        entry.get("_id").cloned()?,
+                    )),
+                    _ => None,
+                })
+                .collect(),
+            Err(_) => HashMap::new(),
+        };
+
+        BulkWriteResult {
+            inserted: reply_count(reply, "nInserted"),
+            matched: reply_count(reply, "nMatched"),
+            modified: reply_count(reply, "nModified"),
+            deleted: reply_count(reply, "nRemoved"),
+            upserted: reply_count(reply, "nUpserted"),
+            inserted_ids,
+            upserted_ids,
+        }
+    }
+}
+
+/// The C driver reports counts as int32, the server may use int64 or double.
+pub(crate) fn reply_count(reply: &Document, key: &str) -> i64 {
+    match reply.get(key) {
+        Some(Bson::I32(count)) => i64::from(*count),
+        Some(Bson::I64(count)) => *count,
+        Some(Bson::FloatingPoint(count)) => *count as i64,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BulkWriteResult, DeleteResult, UpdateResult};
+    use bson::Bson;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_update_result_from_reply() {
+        let reply = doc! { "matchedCount": 2, "modifiedCount": 1i64 };
+        let result = UpdateResult::from_reply(&reply);
+        assert_eq!(2, result.matched);
+        assert_eq!(1, result.modified);
+        assert_eq!(None, result.upserted_id);
+
+        let reply = doc! { "matchedCount": 0, "modifiedCount": 0, "upsertedId": "new" };
+        let result = UpdateResult::from_reply(&reply);
+        assert_eq!(Some(Bson::String("new".to_string())), result.upserted_id);
+    }
+
+    #[test]
+    fn test_delete_result_from_reply() {
+        let result = DeleteResult::from_reply(&doc! { "deletedCount": 3 });
+        assert_eq!(3, result.deleted);
+
+        let result = DeleteResult::from_reply(&doc! {});
+        assert_eq!(0, result.deleted);
+    }
+
+    #[test]
+    fn test_bulk_write_result_from_reply() {
+        let reply = doc! {
+            "nInserted": 2,
+            "nMatched": 1,
+            "nModified": 1,
+            "nRemoved": 0,
+            "nUpserted": 1,
+            "upserted": [{ "index": 3, "_id": "new" }],
+        };
+        let mut inserted_ids = HashMap::new();
+        inserted_ids.insert(0, Bson::I32(1));
+
+        let result = BulkWriteResult::from_reply(&reply, inserted_ids.clone());
+        assert_eq!(2, result.inserted);
+        assert_eq!(1, result.matched);
+        assert_eq!(1, result.upserted);
+        assert_eq!(inserted_ids, result.inserted_ids);
+        assert_eq!(
+            Some(&Bson::String("new".to_string())),
+            result.upserted_ids.get(&3)
+        );
+    }
+}
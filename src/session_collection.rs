@@ -0,0 +1,444 @@
+//! Collections bound to a client session, see `Collection::with_session`.
+
+use crate::{
+    bulk_write::{BulkWrite, BulkWritec, WriteModel},
+    change_stream::{ChangeStream, ChangeStreamc, TypedChangeStreamc},
+    collection::{Collection, Collectionc},
+    cursor::{Cursor, Cursorc},
+    error::Result,
+    explain::{ExplainResult, ExplainVerbosity},
+    index_model::IndexModel,
+    options::{
+        Aggregate, BulkOperation, ChangeStreamOptions, Count, CountDocuments, Distinct,
+        EstimatedDocumentCount, FindAndModify, FindOptions, Insert, Remove, RemoveOne, ReplaceOne,
+        Tail, Update, UpdateOne,
+    },
+    pipeline::Pipeline,
+    read_concern::ReadConcernc,
+    read_prefs::ReadPrefsc,
+    results::{BulkWriteResult, DeleteResult, InsertManyResult, InsertOneResult, UpdateResult},
+    session::Sessionc,
+    tail_cursor::TailCursorc,
+    write_concern::WriteConcernc,
+};
+use bson::{Bson, Document};
+use std::{marker::PhantomData, ops::Deref};
+
+/// A collection running every operation in a session.  It borrows the session, which
+/// the driver reads on each operation.
+///
+/// # Examples
+/// ```compile_fail
+/// use mongo_leaf::prelude::*;
+///
+/// # fn main() -> Result<()> {
+/// let builder = Builder::new();
+/// let pool = builder.connect()?;
+/// let client = pool.pop();
+/// let accounts = client.default_database().get_collection("accounts");
+///
+/// let in_session = {
+///     let session = client.start_session(None)?;
+///     accounts.with_session(&session)
+/// };
+/// in_session.count_documents(bson::Document::new(), None)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SessionCollection<'s> {
+    collection: Collectionc,
+    session: PhantomData<&'s Sessionc>,
+}
+
+impl<'s> SessionCollection<'s> {
+    /// Wraps a collection that appends the session to the opts of its operations.
+    pub(crate) fn new(collection: Collectionc, _session: &'s Sessionc) -> Self {
+        SessionCollection {
+            collection,
+            session: PhantomData,
+        }
+    }
+}
+
+/// A cursor, change stream or bulk write opened in a session, which it can't outlive as
+/// the driver keeps using the session to fetch more results.
+#[derive(Debug)]
+pub struct InSession<'s, T> {
+    inner: T,
+    session: PhantomData<&'s Sessionc>,
+}
+
+impl<T> InSession<'_, T> {
+    fn new(inner: T) -> Self {
+        InSession {
+            inner,
+            session: PhantomData,
+        }
+    }
+}
+
+impl<'s> InSession<'s, ChangeStreamc> {
+    /// Parses the events into `ChangeEvent`s, see `ChangeStreamc::typed`.
+    pub fn typed(self) -> InSession<'s, TypedChangeStreamc> {
+        InSession::new(self.inner.typed())
+    }
+}
+
+impl<T> Deref for InSession<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T: Iterator> Iterator for InSession<'_, T> {
+    type Item = T::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<T: Cursor> Cursor for InSession<'_, T> {
+    type Host = T::Host;
+
+    fn get_hosts(&self) -> Option<Vec<Self::Host>> {
+        self.inner.get_hosts()
+    }
+}
+
+impl<T: ChangeStream> ChangeStream for InSession<'_, T> {
+    fn resume_token(&self) -> Option<Document> {
+        self.inner.resume_token()
+    }
+}
+
+impl<T: BulkWrite> BulkWrite for InSession<'_, T> {
+    fn append(&mut self, model: WriteModel) -> Result<&mut Self> {
+        self.inner.append(model)?;
+        Ok(self)
+    }
+
+    fn execute(self) -> Result<BulkWriteResult> {
+        self.inner.execute()
+    }
+}
+
+impl<'s> Collection for SessionCollection<'s> {
+    type Cursor = InSession<'s, Cursorc>;
+    type ChangeStream = InSession<'s, ChangeStreamc>;
+    type BulkWrite = InSession<'s, BulkWritec>;
+    type TailCursor = TailCursorc;
+
+    fn count(&self, filter: Option<bson::Document>) -> Result<i64> {
+        self.collection.count(filter)
+    }
+
+    fn count_with_opts(&self, filter: Option<bson::Document>, opts: Option<Count>) -> Result<i64> {
+        self.collection.count_with_opts(filter, opts)
+    }
+
+    fn count_documents(&self, filter: bson::Document, opts: Option<CountDocuments>) -> Result<i64> {
+        self.collection.count_documents(filter, opts)
+    }
+
+    fn estimated_document_count(&self, opts: Option<EstimatedDocumentCount>) -> Result<i64> {
+        self.collection.estimated_document_count(opts)
+    }
+
+    fn distinct(
+        &self,
+        field: &str,
+        filter: Option<bson::Document>,
+        opts: Option<Distinct>,
+    ) -> Result<Vec<Bson>> {
+        self.collection.distinct(field, filter, opts)
+    }
+
+    fn insert_one(&self, doc: bson::Document) -> Result<InsertOneResult> {
+        self.collection.insert_one(doc)
+    }
+
+    fn insert_one_with_opts(
+        &self,
+        doc: bson::Document,
+        opts: Option<Insert>,
+    ) -> Result<InsertOneResult> {
+        self.collection.insert_one_with_opts(doc, opts)
+    }
+
+    fn insert_many(&self, docs: Vec<bson::Document>) -> Result<InsertManyResult> {
+        self.collection.insert_many(docs)
+    }
+
+    fn insert_many_with_opts(
+        &self,
+        docs: Vec<bson::Document>,
+        opts: Option<Insert>,
+    ) -> Result<InsertManyResult> {
+        self.collection.insert_many_with_opts(docs, opts)
+    }
+
+    fn delete(&self, selector: bson::Document) -> Result<DeleteResult> {
+        self.collection.delete(selector)
+    }
+
+    fn delete_with_opts(
+        &self,
+        selector: bson::Document,
+        opts: Option<Remove>,
+    ) -> Result<DeleteResult> {
+        self.collection.delete_with_opts(selector, opts)
+    }
+
+    fn update(&self, selector: bson::Document, update: bson::Document) -> Result<UpdateResult> {
+        self.collection.update(selector, update)
+    }
+
+    fn update_with_opts(
+        &self,
+        selector: bson::Document,
+        update: bson::Document,
+        opts: Option<Update>,
+    ) -> Result<UpdateResult> {
+        self.collection.update_with_opts(selector, update, opts)
+    }
+
+    fn update_one(&self, selector: bson::Document, update: bson::Document) -> Result<UpdateResult> {
+        self.collection.update_one(selector, update)
+    }
+
+    fn update_one_with_opts(
+        &self,
+        selector: bson::Document,
+        update: bson::Document,
+        opts: Option<UpdateOne>,
+    ) -> Result<UpdateResult> {
+        self.collection.update_one_with_opts(selector, update, opts)
+    }
+
+    fn replace_one(
+        &self,
+        selector: bson::Document,
+        replacement: bson::Document,
+    ) -> Result<UpdateResult> {
+        self.collection.replace_one(selector, replacement)
+    }
+
+    fn replace_one_with_opts(
+        &self,
+        selector: bson::Document,
+        replacement: bson::Document,
+        opts: Option<ReplaceOne>,
+    ) -> Result<UpdateResult> {
+        self.collection
+            .replace_one_with_opts(selector, replacement, opts)
+    }
+
+    fn delete_one(&self, selector: bson::Document) -> Result<DeleteResult> {
+        self.collection.delete_one(selector)
+    }
+
+    fn delete_one_with_opts(
+        &self,
+        selector: bson::Document,
+        opts: Option<RemoveOne>,
+    ) -> Result<DeleteResult> {
+        self.collection.delete_one_with_opts(selector, opts)
+    }
+
+    fn find_one_and_update(
+        &self,
+        filter: bson::Document,
+        update: bson::Document,
+    ) -> Result<Option<bson::Document>> {
+        self.collection.find_one_and_update(filter, update)
+    }
+
+    fn find_one_and_update_with_opts(
+        &self,
+        filter: bson::Document,
+        update: bson::Document,
+        opts: Option<FindAndModify>,
+    ) -> Result<Option<bson::Document>> {
+        self.collection
+            .find_one_and_update_with_opts(filter, update, opts)
+    }
+
+    fn find_one_and_replace(
+        &self,
+        filter: bson::Document,
+        replacement: bson::Document,
+    ) -> Result<Option<bson::Document>> {
+        self.collection.find_one_and_replace(filter, replacement)
+    }
+
+    fn find_one_and_replace_with_opts(
+        &self,
+        filter: bson::Document,
+        replacement: bson::Document,
+        opts: Option<FindAndModify>,
+    ) -> Result<Option<bson::Document>> {
+        self.collection
+            .find_one_and_replace_with_opts(filter, replacement, opts)
+    }
+
+    fn find_one_and_delete(&self, filter: bson::Document) -> Result<Option<bson::Document>> {
+        self.collection.find_one_and_delete(filter)
+    }
+
+    fn find_one_and_delete_with_opts(
+        &self,
+        filter: bson::Document,
+        opts: Option<FindAndModify>,
+    ) -> Result<Option<bson::Document>> {
+        self.collection.find_one_and_delete_with_opts(filter, opts)
+    }
+
+    fn find(&self, filter: bson::Document) -> Self::Cursor {
+        InSession::new(self.collection.find(filter))
+    }
+
+    fn find_with_opts(&self, filter: bson::Document, opts: Option<FindOptions>) -> Self::Cursor {
+        InSession::new(self.collection.find_with_opts(filter, opts))
+    }
+
+    fn tail(&self, filter: bson::Document, opts: Tail) -> Self::TailCursor {
+        self.collection.tail(filter, opts)
+    }
+
+    fn aggregate(&self, pipeline: impl Into<Pipeline>) -> Self::Cursor {
+        InSession::new(self.collection.aggregate(pipeline))
+    }
+
+    fn aggregate_with_opts(
+        &self,
+        pipeline: impl Into<Pipeline>,
+        opts: Option<Aggregate>,
+    ) -> Self::Cursor {
+        InSession::new(self.collection.aggregate_with_opts(pipeline, opts))
+    }
+
+    fn drop_collection(&self) -> Result<bool> {
+        self.collection.drop_collection()
+    }
+
+    fn with_session<'t>(&self, session: &'t Sessionc) -> SessionCollection<'t> {
+        self.collection.with_session(session)
+    }
+
+    fn read_prefs(&self) -> ReadPrefsc {
+        self.collection.read_prefs()
+    }
+
+    fn set_read_prefs(&self, read_prefs: &ReadPrefsc) {
+        self.collection.set_read_prefs(read_prefs)
+    }
+
+    fn read_concern(&self) -> ReadConcernc {
+        self.collection.read_concern()
+    }
+
+    fn set_read_concern(&self, read_concern: &ReadConcernc) {
+        self.collection.set_read_concern(read_concern)
+    }
+
+    fn write_concern(&self) -> WriteConcernc {
+        self.collection.write_concern()
+    }
+
+    fn set_write_concern(&self, write_concern: &WriteConcernc) {
+        self.collection.set_write_concern(write_concern)
+    }
+
+    fn explain_find(
+        &self,
+        filter: bson::Document,
+        opts: Option<FindOptions>,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult> {
+        self.collection.explain_find(filter, opts, verbosity)
+    }
+
+    fn explain_aggregate(
+        &self,
+        pipeline: impl Into<Pipeline>,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult> {
+        self.collection.explain_aggregate(pipeline, verbosity)
+    }
+
+    fn explain_count(
+        &self,
+        filter: bson::Document,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult> {
+        self.collection.explain_count(filter, verbosity)
+    }
+
+    fn explain_distinct(
+        &self,
+        field: &str,
+        filter: bson::Document,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult> {
+        self.collection.explain_distinct(field, filter, verbosity)
+    }
+
+    fn explain_update(
+        &self,
+        selector: bson::Document,
+        update: bson::Document,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult> {
+        self.collection.explain_update(selector, update, verbosity)
+    }
+
+    fn explain_delete(
+        &self,
+        selector: bson::Document,
+        verbosity: ExplainVerbosity,
+    ) -> Result<ExplainResult> {
+        self.collection.explain_delete(selector, verbosity)
+    }
+
+    fn bulk_write(&self) -> Result<Self::BulkWrite> {
+        self.collection.bulk_write().map(InSession::new)
+    }
+
+    fn bulk_write_with_opts(&self, opts: Option<BulkOperation>) -> Result<Self::BulkWrite> {
+        self.collection
+            .bulk_write_with_opts(opts)
+            .map(InSession::new)
+    }
+
+    fn create_index(&self, index: IndexModel) -> Result<String> {
+        self.collection.create_index(index)
+    }
+
+    fn create_indexes(&self, indexes: Vec<IndexModel>) -> Result<Vec<String>> {
+        self.collection.create_indexes(indexes)
+    }
+
+    fn list_indexes(&self) -> Result<Vec<IndexModel>> {
+        self.collection.list_indexes()
+    }
+
+    fn drop_index(&self, name: &str) -> Result<()> {
+        self.collection.drop_index(name)
+    }
+
+    fn drop_indexes(&self) -> Result<()> {
+        self.collection.drop_indexes()
+    }
+
+    fn watch(
+        &self,
+        pipeline: Option<bson::Document>,
+        opts: Option<ChangeStreamOptions>,
+    ) -> Result<Self::ChangeStream> {
+        self.collection.watch(pipeline, opts).map(InSession::new)
+    }
+}
@@ -0,0 +1,233 @@
+//! Tailable cursors that keep following new documents of a capped collection.
+
+use crate::{
+    collection::{Collection, Collectionc},
+    cursor::Cursorc,
+    error::Result,
+    options::{FindOptions, Tail},
+    worker::{Fetched, Worker},
+};
+use bson::{Bson, Document};
+use futures::stream::Stream;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    thread,
+};
+
+#[derive(Debug)]
+pub struct TailCursorc {
+    collection: Collectionc,
+    filter: Document,
+    opts: Tail,
+    cursor: Option<Cursorc>,
+    last_id: Option<Bson>,
+    retries: u32,
+    done: bool,
+}
+
+pub trait TailCursor {
+    /// The `_id` of the last document returned, the cursor resumes after it.
+    fn last_id(&self) -> Option<&Bson>;
+}
+
+impl TailCursorc {
+    pub(crate) fn new(collection: Collectionc, filter: Document, opts: Tail) -> Self {
+        TailCursorc {
+            collection,
+            filter,
+            opts,
+            cursor: None,
+            last_id: None,
+            retries: 0,
+            done: false,
+        }
+    }
+
+    /// Opens a tailable cursor for the documents after the last seen `_id`.
+    fn open(&self) -> Cursorc {
+        let filter = match self.last_id {
+            Some(ref id) => {
+                doc! { "$and": [self.filter.clone(), { "_id": { "$gt": id.clone() } }] }
+            }
+            None => self.filter.clone(),
+        };
+
+        let opts = FindOptions {
+            tailable: true,
+            await_data: true,
+            ..Default::default()
+        };
+
+        self.collection.find_with_opts(filter, Some(opts))
+    }
+
+    /// Reads the next document without waiting, `Pending` when none has arrived yet.
+    fn poll_document(&mut self) -> Poll<Option<Result<Document>>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+
+        if self.cursor.is_none() {
+            self.cursor = Some(self.open());
+        }
+        let cursor = self.cursor.as_mut().expect("cursor was just opened");
+
+        match cursor.next() {
+            Some(Ok(doc)) => {
+                self.last_id = doc.get("_id").cloned();
+                self.retries = 0;
+                Poll::Ready(Some(Ok(doc)))
+            }
+            Some(Err(err)) => {
+                self.cursor = None;
+                self.retries += 1;
+
+                if self.retries > self.opts.max_retries {
+                    self.done = true;
+                    Poll::Ready(Some(Err(err)))
+                } else {
+                    Poll::Pending
+                }
+            }
+            None => {
+                // A tailable cursor dies when the collection was empty or the last seen
+                // document was overwritten, it is opened again on the next poll.
+                if !cursor.more() {
+                    self.cursor = None;
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl TailCursor for TailCursorc {
+    fn last_id(&self) -> Option<&Bson> {
+        self.last_id.as_ref()
+    }
+}
+
+/// Blocks until the next document arrives.  Only ends after `max_retries` consecutive
+/// errors, the last error being returned before `None`.  Use `PooledCollection::tail` to
+/// wait in async code.
+impl Iterator for TailCursorc {
+    type Item = Result<Document>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.poll_document() {
+                Poll::Ready(item) => return item,
+                Poll::Pending => thread::sleep(self.opts.wait_duration),
+            }
+        }
+    }
+}
+
+/// A tailable cursor waiting for documents on a worker thread, so polling never blocks
+/// the executor.  The thread owns the pooled client the cursor was opened with, the
+/// client goes back to the pool after the cursor is dropped, as soon as the thread's
+/// current wait returns.
+///
+/// # Examples
+/// ```no_run
+/// #[macro_use]
+/// extern crate bson;
+/// use futures::{executor::block_on, stream::StreamExt};
+/// use mongo_leaf::prelude::*;
+/// use std::{env, sync::Arc};
+///
+/// # fn main() -> Result<()> {
+/// env::set_var("MONGODB_URI","mongodb://standard");
+/// let builder = Builder::new();
+/// let pool = Arc::new(builder.random_database_connect()?);
+/// let client = pool.pop();
+/// let db = client.default_database();
+/// client.command_simple(db.name(), doc!{"create": "log", "capped": true, "size": 4096}, None)?;
+///
+/// let collection = pool.pop_pooled().default_database().collection("log");
+/// let mut tail = collection.tail(doc!{"level": "info"}, Tail::default());
+/// db.get_collection("log").insert_one(doc!{"level": "info", "msg": "started"})?;
+///
+/// let first = block_on(StreamExt::next(&mut tail)).unwrap()?;
+/// assert_eq!("started", first.get_str("msg")?);
+/// assert_eq!(first.get("_id"), tail.last_id());
+///
+/// # db.destroy();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PooledTailCursor {
+    worker: Worker<Result<Document>>,
+    last_id: Option<Bson>,
+}
+
+impl PooledTailCursor {
+    /// Starts tailing on the worker thread, `open` returns the cursor along with the
+    /// pooled handle it was opened with, which is dropped after the cursor.
+    pub(crate) fn open<H, O>(open: O) -> Self
+    where
+        O: FnOnce() -> Result<(TailCursorc, H)> + Send + 'static,
+    {
+        PooledTailCursor {
+            worker: Worker::open(open, |(tail, _): &mut (TailCursorc, H)| {
+                match tail.poll_document() {
+                    Poll::Ready(Some(doc)) => Fetched::Item(doc),
+                    Poll::Ready(None) => Fetched::Done,
+                    Poll::Pending => {
+                        // Returns between waits, so dropping the cursor stops the thread.
+                        thread::sleep(tail.opts.wait_duration);
+                        Fetched::Empty
+                    }
+                }
+            }),
+            last_id: None,
+        }
+    }
+
+    fn fetched(&mut self, doc: Result<Document>) -> Result<Document> {
+        if let Ok(ref doc) = doc {
+            self.last_id = doc.get("_id").cloned();
+        }
+        doc
+    }
+}
+
+impl TailCursor for PooledTailCursor {
+    fn last_id(&self) -> Option<&Bson> {
+        self.last_id.as_ref()
+    }
+}
+
+/// Blocks until the next document arrives, see `TailCursorc`.
+impl Iterator for PooledTailCursor {
+    type Item = Result<Document>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.worker.wait() {
+                Fetched::Item(doc) => return Some(self.fetched(doc)),
+                Fetched::Empty => continue,
+                Fetched::Done => return None,
+            }
+        }
+    }
+}
+
+impl Stream for PooledTailCursor {
+    type Item = Result<Document>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            return match this.worker.poll(cx) {
+                Poll::Ready(Fetched::Item(doc)) => Poll::Ready(Some(this.fetched(doc))),
+                Poll::Ready(Fetched::Empty) => continue,
+                Poll::Ready(Fetched::Done) => Poll::Ready(None),
+                Poll::Pending => Poll::Pending,
+            };
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Runs the blocking calls behind the async streams on a dedicated thread.
+
+use crate::error::Result;
+use std::{
+    sync::{
+        mpsc::{self, Receiver, Sender, TryRecvError},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+/// Outcome of a single blocking call.
+#[derive(Debug)]
+pub(crate) enum Fetched<T> {
+    Item(T),
+    /// Nothing arrived yet but more may come, e.g. on a tailable cursor
+    Empty,
+    /// Nothing will ever come
+    Done,
+}
+
+/// A thread running one blocking call per request and waking the polling task when the
+/// result is ready.  Dropping the worker returns right away, the thread finishes the
+/// current call and then drops its state, including the pooled client it owns.
+#[derive(Debug)]
+pub(crate) struct Worker<T> {
+    /// Closed when the worker is dropped, which stops the thread
+    requests: Sender<()>,
+    results: Receiver<Fetched<T>>,
+    waker: Arc<Mutex<Option<Waker>>>,
+    in_flight: bool,
+}
+
+impl<T: Send + 'static> Worker<T> {
+    /// Starts the thread, the state is created by `open` and dropped on the thread, so
+    /// it never needs to be `Send`.  `fetch` runs with it for every request.
+    fn start<S, O, F>(open: O, mut fetch: F) -> Self
+    where
+        O: FnOnce() -> S + Send + 'static,
+        F: FnMut(&mut S) -> Fetched<T> + Send + 'static,
+    {
+        let (requests, thread_requests) = mpsc::channel::<()>();
+        let (thread_results, results) = mpsc::channel();
+        let waker = Arc::new(Mutex::new(None::<Waker>));
+        let thread_waker = waker.clone();
+
+        // Not joined, so dropping a stream never blocks the executor.
+        thread::spawn(move || {
+            let mut state = open();
+
+            while thread_requests.recv().is_ok() {
+                if thread_results.send(fetch(&mut state)).is_err() {
+                    break;
+                }
+
+                if let Some(waker) = thread_waker.lock().expect("waker lock").take() {
+                    waker.wake();
+                }
+            }
+        });
+
+        Worker {
+            requests,
+            results,
+            waker,
+            in_flight: false,
+        }
+    }
+
+    /// Returns the result of the call in flight, or starts one.
+    pub(crate) fn poll(&mut self, cx: &mut Context) -> Poll<Fetched<T>> {
+        // Registered before checking for a result, the thread wakes after sending one.
+        *self.waker.lock().expect("waker lock") = Some(cx.waker().clone());
+
+        if !self.in_flight {
+            self.request();
+            return Poll::Pending;
+        }
+
+        match self.results.try_recv() {
+            Ok(fetched) => {
+                self.in_flight = false;
+                Poll::Ready(fetched)
+            }
+            Err(TryRecvError::Empty) => Poll::Pending,
+            Err(TryRecvError::Disconnected) => Poll::Ready(Fetched::Done),
+        }
+    }
+
+    /// Blocks until the result of the next call.
+    pub(crate) fn wait(&mut self) -> Fetched<T> {
+        if !self.in_flight {
+            self.request();
+        }
+        self.in_flight = false;
+
+        self.results.recv().unwrap_or(Fetched::Done)
+    }
+
+    fn request(&mut self) {
+        self.in_flight = self.requests.send(()).is_ok();
+    }
+}
+
+impl<T: Send + 'static> Worker<Result<T>> {
+    /// Starts the thread with the driver handle returned by `open`, which also holds the
+    /// client the handle uses.  An error of `open` is the only result, the handle is
+    /// dropped after the first error `fetch` returns, both end the results.
+    pub(crate) fn open<S, O, F>(open: O, mut fetch: F) -> Self
+    where
+        O: FnOnce() -> Result<S> + Send + 'static,
+        F: FnMut(&mut S) -> Fetched<Result<T>> + Send + 'static,
+    {
+        Worker::start(
+            move || open().map_err(Some),
+            move |state: &mut std::result::Result<S, Option<failure::Error>>| {
+                let fetched = match *state {
+                    Ok(ref mut handle) => fetch(handle),
+                    Err(ref mut error) => {
+                        return error
+                            .take()
+                            .map_or(Fetched::Done, |err| Fetched::Item(Err(err)))
+                    }
+                };
+
+                if let Fetched::Item(Err(_)) = fetched {
+                    *state = Err(None);
+                }
+                fetched
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Fetched, Worker};
+    use crate::error::Result;
+    use futures::{executor::block_on, future::poll_fn};
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            mpsc, Arc,
+        },
+        task::Poll,
+        thread,
+        time::Duration,
+    };
+
+    /// Sets the flag when dropped, standing in for a driver handle.
+    struct Handle(Arc<AtomicBool>);
+
+    impl Drop for Handle {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_worker_wakes_and_stops() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let handle = Handle(dropped.clone());
+        let mut count = 0;
+
+        let mut worker = Worker::open(
+            move || Ok(handle),
+            move |_: &mut Handle| {
+                count += 1;
+                thread::sleep(Duration::from_millis(10));
+                match count {
+                    1 => Fetched::Empty,
+                    2 => Fetched::Item(Ok(count)),
+                    _ => Fetched::Done,
+                }
+            },
+        );
+
+        let item = block_on(poll_fn(|cx| loop {
+            match worker.poll(cx) {
+                Poll::Ready(Fetched::Empty) => continue,
+                Poll::Ready(Fetched::Item(item)) => return Poll::Ready(item.ok()),
+                Poll::Ready(Fetched::Done) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }));
+        assert_eq!(Some(2), item);
+
+        match worker.wait() {
+            Fetched::Done => {}
+            fetched => panic!("expected the end, got {:?}", fetched),
+        }
+        assert!(!dropped.load(Ordering::SeqCst));
+
+        drop(worker);
+        for _ in 0..100 {
+            if dropped.load(Ordering::SeqCst) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("the thread kept the handle");
+    }
+
+    #[test]
+    fn test_worker_drop_does_not_wait_for_call() {
+        let (release, released) = mpsc::channel::<()>();
+        let (started, has_started) = mpsc::channel();
+
+        let mut worker = Worker::<Result<i32>>::open(
+            || Ok(()),
+            move |_| {
+                started.send(()).ok();
+                released.recv().ok();
+                Fetched::Empty
+            },
+        );
+        let polled = block_on(poll_fn(|cx| Poll::Ready(worker.poll(cx).is_pending())));
+        assert!(polled);
+        has_started.recv().unwrap();
+
+        // Returns while the call is still running.
+        drop(worker);
+        release.send(()).unwrap();
+    }
+
+    #[test]
+    fn test_worker_returns_open_error_once() {
+        let mut worker = Worker::<Result<i32>>::open(
+            || -> Result<()> { Err(failure::err_msg("no client")) },
+            |_| Fetched::Empty,
+        );
+
+        match worker.wait() {
+            Fetched::Item(Err(err)) => assert_eq!("no client", err.to_string()),
+            fetched => panic!("expected the error, got {:?}", fetched),
+        }
+        match worker.wait() {
+            Fetched::Done => {}
+            fetched => panic!("expected the end, got {:?}", fetched),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! Abstraction on top of the MongoDB connection write concern.
 
-use crate::bindings;
+use crate::{bindings, bsonc::Bsonc};
 use std::ffi::CString;
 use std::ptr;
 
@@ -24,6 +24,20 @@ pub struct WriteConcernc {
     inner: *mut bindings::mongoc_write_concern_t,
 }
 
+impl WriteConcernc {
+    /// Copies a write concern owned by the C driver.
+    pub(crate) fn copy_from_ptr(ptr: *const bindings::mongoc_write_concern_t) -> Self {
+        assert!(!ptr.is_null());
+        let inner = unsafe { bindings::mongoc_write_concern_copy(ptr) };
+        assert!(!inner.is_null());
+        WriteConcernc { inner }
+    }
+
+    pub fn append(&self, opts: &mut Bsonc) -> bool {
+        unsafe { bindings::mongoc_write_concern_append(self.inner, opts.as_mut_ptr()) }
+    }
+}
+
 pub trait WriteConcern {
     fn new(level: WriteConcernLevel, timeout: Option<i64>) -> Self;
     fn as_ptr(&self) -> *const bindings::mongoc_write_concern_t {
@@ -87,6 +101,10 @@ impl WriteConcern for WriteConcernc {
     }
 }
 
+// A WriteConcernc owns its mongoc_write_concern_t.  The driver caches the BSON form of the
+// write concern inside it on first use, so it is not Sync, but moving it moves the cache too.
+unsafe impl Send for WriteConcernc {}
+
 impl Drop for WriteConcernc {
     fn drop(&mut self) {
         assert!(!self.inner.is_null());